@@ -1,19 +1,33 @@
-use std::sync::Arc;
+use std::cmp::Ordering;
+use std::sync::{Arc, RwLock};
 
-use super::functions::Function;
+use super::functions::{BuiltInFunction, Function};
 use super::literal::Literal;
 
+/// A prolang value.
+///
+/// More kinds of values may be added, and the hidden variants only ever exist
+/// while a program runs, so embedders should match with a wildcard arm.
 #[derive(Debug, Clone)]
-pub(crate) enum DataType {
+#[non_exhaustive]
+pub enum DataType {
     Integer(i128),
     Float(f64),
     Boolean(bool),
     Infinity,
-    InternalUndefined,
     String(Arc<String>),
     Function(Arc<Function>),
+    BuiltIn(BuiltInFunction),
+    // shared by every value it is given to, copied by the one that changes it while it is shared
+    List(Arc<RwLock<Vec<Literal>>>),
+    // keys in the order they were first inserted, shared like lists
+    Map(Arc<RwLock<Vec<(String, Literal)>>>),
+    // how `return`, `break` and `skip` travel up to the function or loop they end
+    #[doc(hidden)]
     Return(Box<Literal>),
+    #[doc(hidden)]
     Break(Box<Literal>),
+    #[doc(hidden)]
     Skip(Box<Literal>),
 }
 
@@ -23,13 +37,58 @@ impl PartialEq for DataType {
             (DataType::String(a), DataType::String(b)) => a == b,
             (DataType::Float(a), DataType::Float(b)) => a == b,
             (DataType::Integer(a), DataType::Integer(b)) => a == b,
+            (DataType::Integer(a), DataType::Float(b))
+            | (DataType::Float(b), DataType::Integer(a)) => {
+                compare_integer_to_float(*a, *b) == Some(Ordering::Equal)
+            }
             (DataType::Boolean(a), DataType::Boolean(b)) => a == b,
             (DataType::Infinity, DataType::Infinity) => true,
+            (DataType::Function(a), DataType::Function(b)) => Arc::ptr_eq(a, b),
+            (DataType::BuiltIn(a), DataType::BuiltIn(b)) => a == b,
+            (DataType::List(a), DataType::List(b)) => {
+                Arc::ptr_eq(a, b) || *a.read().unwrap() == *b.read().unwrap()
+            }
+            // the order of the keys does not matter
+            (DataType::Map(a), DataType::Map(b)) => {
+                if Arc::ptr_eq(a, b) {
+                    return true;
+                }
+                let (a, b) = (a.read().unwrap(), b.read().unwrap());
+                a.len() == b.len()
+                    && a.iter().all(|(key, value)| {
+                        b.iter().any(|(other_key, other_value)| {
+                            key == other_key && value == other_value
+                        })
+                    })
+            }
             _ => false,
         }
     }
 }
 
+impl DataType {
+    // `is` compares functions, lists and maps by reference, every other value is its own identity
+    pub(crate) fn is_identical(&self, other: &Self) -> bool {
+        match (self, other) {
+            (DataType::Function(a), DataType::Function(b)) => Arc::ptr_eq(a, b),
+            (DataType::List(a), DataType::List(b)) => Arc::ptr_eq(a, b),
+            (DataType::Map(a), DataType::Map(b)) => Arc::ptr_eq(a, b),
+            (a, b) => a == b,
+        }
+    }
+}
+
+// integers and floats are compared by their values, so `1 == 1.0`.
+// `None` when the float is not a number
+pub(crate) fn compare_integer_to_float(integer: i128, float: f64) -> Option<Ordering> {
+    if float.fract() == 0.0 && float.abs() < i128::MAX as f64 {
+        // exact, even for integers too large for a float to hold
+        Some(integer.cmp(&(float as i128)))
+    } else {
+        (integer as f64).partial_cmp(&float)
+    }
+}
+
 impl std::fmt::Display for DataType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let name = match self {
@@ -37,9 +96,10 @@ impl std::fmt::Display for DataType {
             DataType::Float(_) => "Float",
             DataType::Boolean(_) => "Boolean",
             DataType::Infinity => "Infinity",
-            DataType::InternalUndefined => "Undefined",
             DataType::String(_) => "String",
-            DataType::Function(_) => "Function",
+            DataType::Function(_) | DataType::BuiltIn(_) => "Function",
+            DataType::List(_) => "List",
+            DataType::Map(_) => "Map",
             DataType::Return(_) => "return",
             DataType::Break(_) => "break",
             DataType::Skip(_) => "skip",
@@ -2,46 +2,59 @@ use std::fmt::Display;
 
 use crate::common::literal::Literal;
 use crate::common::operators::Operator;
+use crate::common::span::Span;
 use crate::lexing::symbols::Symbol;
 use crate::lexing::token::TokenKind;
 
+#[derive(Debug)]
 pub enum CompilerError {
     // NoTokensAvailable,
 
-    // // Lexical Errors
-    // InvalidCharacter(
-    //     char,  // character
-    //     usize, // line
-    //     usize, // column
-    // ),
-    // InvalidNumber(
-    //     String, // number
-    //     usize,  // line
-    //     usize,  // column
-    // ),
-    // UnterminatedString(usize, usize),
+    // the source could not be read to the end
+    ReadError(std::io::Error),
+
+    // Lexical Errors
+    InvalidCharacter(
+        char, // character
+        Span,
+    ),
+    InvalidNumber(
+        String, // number
+        Span,
+    ),
+    UnterminatedString(Span),
     // InvalidKeyword,
-    // InvalidUtf8Character,
+    InvalidUtf8Character(Span),
+    UnterminatedComment(Span),
+    InvalidEscapeSequence(
+        String, // sequence
+        Span,
+    ),
+    TooManyRawStringHashes(
+        usize, // the most there can be
+        Span,
+    ),
 
     // Syntax Errors
     UnexpectedToken(
         TokenKind, // Token
-        usize,     // line
-        usize,     // column
+        Span,
     ),
     UnexpectedTokenWithExpected(
         TokenKind, // Unexpected Token
         TokenKind, // Expected Token
-        usize,     // line
-        usize,     // column
+        Span,
     ),
     InvalidOperationAsAssignmentOperation,
     CannotConvertFromImmutableToMutable,
     UnInitializedVariable(String),
     MissingArrow(
         TokenKind, // Token
-        usize,     // line
-        usize,     // column
+        Span,
+    ),
+    ExpectedEndOfStatement(
+        TokenKind, // Token
+        Span,
     ),
 
     // Evaluation Errors
@@ -63,6 +76,7 @@ pub enum CompilerError {
         Literal,  // Right
     ),
     MathUndefined,
+    IntegerOverflow(Operator),
     InvalidUseOfMutableKeyword,
     ImmutableVariable(String),
     OperationOnFunction,
@@ -80,30 +94,104 @@ pub enum CompilerError {
     SkipOutsideLoop,
     SkipCountTypeMisMatch(String),
     InvalidType(String),
+    InvalidIndex(String),
+    NotIndexable(String),
+    InvalidKey(String),
+    NotAssignable(String),
+    UndefinedKey(String),
+    IndexOutOfRange(
+        i128,  // index
+        usize, // length
+    ),
+    InvalidRangeBound(String),
+    RangeStepZero,
+    RangeTooLong(u128),
+    NotIterable(String),
+    CallDepthExceeded(usize),
+    // a runtime error and the expression that raised it
+    Located(Box<CompilerError>, Span),
+    // every error found in one run, in the order they were found
+    Multiple(Vec<CompilerError>),
+}
+
+impl std::error::Error for CompilerError {}
 
-    // Internal Errors
-    OperationOnUndefined,
+impl CompilerError {
+    pub(crate) fn from_errors(mut errors: Vec<CompilerError>) -> Result<(), CompilerError> {
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            _ => Err(CompilerError::Multiple(errors)),
+        }
+    }
+
+    // splits `Multiple` back into the errors it holds
+    pub(crate) fn into_errors(self) -> Vec<CompilerError> {
+        match self {
+            CompilerError::Multiple(errors) => errors,
+            error => vec![error],
+        }
+    }
+
+    /// Where the error is in the source, if it is known.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            CompilerError::InvalidCharacter(_, span)
+            | CompilerError::InvalidNumber(_, span)
+            | CompilerError::UnterminatedString(span)
+            | CompilerError::UnterminatedComment(span)
+            | CompilerError::InvalidEscapeSequence(_, span)
+            | CompilerError::TooManyRawStringHashes(_, span)
+            | CompilerError::InvalidUtf8Character(span)
+            | CompilerError::UnexpectedToken(_, span)
+            | CompilerError::UnexpectedTokenWithExpected(_, _, span)
+            | CompilerError::MissingArrow(_, span)
+            | CompilerError::ExpectedEndOfStatement(_, span)
+            | CompilerError::Located(_, span) => Some(*span),
+            CompilerError::Multiple(errors) => errors.first().and_then(CompilerError::span),
+            _ => None,
+        }
+    }
+
+    // points the error at the given span, unless it already points somewhere
+    pub(crate) fn at(self, span: Span) -> Self {
+        if self.span().is_some() {
+            self
+        } else {
+            CompilerError::Located(Box::new(self), span)
+        }
+    }
 }
 
 impl Display for CompilerError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let text = match self {
             // CompilerError::NoTokensAvailable => "No tokens available".to_string(),
-            // CompilerError::InvalidCharacter(character, line, column) => format!(
-            //     "Invalid character '{}' at line {}, column {}",
-            //     character, line, column
-            // ),
-            // CompilerError::InvalidNumber(number, line, column) => format!(
-            //     "Invalid number '{}' at line {}, column {}",
-            //     number, line, column
-            // ),
-            // CompilerError::UnterminatedString(line, column) => {
-            //     format!("Unterminated string at line {}, column {}", line, column)
-            // }
-            CompilerError::UnexpectedToken(token, line, column) => format!(
-                "Unexpected token '{}' at line {}, column {}",
-                token, line, column
-            ),
+            CompilerError::ReadError(err) => format!("Unable to read the source: {}", err),
+            CompilerError::InvalidCharacter(character, span) => {
+                format!("Invalid character {:?} at {}", character, span)
+            }
+            CompilerError::InvalidNumber(number, span) => {
+                format!("Invalid number '{}' at {}", number, span)
+            }
+            CompilerError::UnterminatedString(span) => {
+                format!("Unterminated string at {}", span)
+            }
+            CompilerError::UnterminatedComment(span) => {
+                format!("Unterminated block comment at {}", span)
+            }
+            CompilerError::InvalidEscapeSequence(sequence, span) => {
+                format!("Invalid escape sequence '{}' at {}", sequence, span)
+            }
+            CompilerError::TooManyRawStringHashes(most, span) => {
+                format!(
+                    "Raw strings can start with at most {} `#`s at {}",
+                    most, span
+                )
+            }
+            CompilerError::UnexpectedToken(token, span) => {
+                format!("Unexpected token '{}' at {}", token, span)
+            }
             CompilerError::InvalidOperatorForBinaryOperation(operator) => {
                 format!("Invalid operator '{}' for binary operation", operator)
             }
@@ -127,10 +215,12 @@ impl Display for CompilerError {
                 )
             }
             CompilerError::MathUndefined => "Math Error: undefined".to_string(),
-            CompilerError::OperationOnUndefined => {
-                panic!("operation on undefined. this should not be happening, place an issue")
+            CompilerError::IntegerOverflow(operator) => {
+                format!("Math Error: the result of {operator} is too large for an integer")
+            }
+            CompilerError::InvalidUtf8Character(span) => {
+                format!("Invalid UTF-8 character at {}", span)
             }
-            // CompilerError::InvalidUtf8Character => "Invalid UTF-8 character".to_string(),
             CompilerError::InvalidUseOfMutableKeyword => {
                 "Invalid use of `mutable` keyword".to_string()
             }
@@ -144,19 +234,21 @@ impl Display for CompilerError {
                 "Cannot convert from Immutable to Mutable".to_string()
             }
             CompilerError::UnInitializedVariable(name) => format!("Uninitialized variable {name}"),
-            CompilerError::UnexpectedTokenWithExpected(unexpected, expected, line, column) => {
+            CompilerError::UnexpectedTokenWithExpected(unexpected, expected, span) => {
                 format!(
-                    "Unexpected token '{}' at line {}, column {}. Expected {}",
-                    unexpected, line, column, expected
+                    "Unexpected token '{}' at {}. Expected {}",
+                    unexpected, span, expected
                 )
             }
             CompilerError::InvalidSeperator(seperator) => format!("invalid seperator {seperator}"),
             CompilerError::InvalidEncloser(encloser) => format!("invalid encloser {encloser}"),
             CompilerError::UndefinedFunction(name) => format!("Undefined function {name}"),
-            CompilerError::MissingArrow(token, line, column) => format!(
-                "Expected arrow `=>` but got '{}' at line {}, column {}",
-                token, line, column
-            ),
+            CompilerError::MissingArrow(token, span) => {
+                format!("Expected arrow `=>` but got '{}' at {}", token, span)
+            }
+            CompilerError::ExpectedEndOfStatement(token, span) => {
+                format!("Expected a new line or `;` before '{}' at {}", token, span)
+            }
             CompilerError::OperationOnFunction => {
                 "Cannot perform operation on a `Function`".to_string()
             }
@@ -190,6 +282,41 @@ impl Display for CompilerError {
             CompilerError::InvalidType(received_type) => {
                 format!("Invalid type {received_type}")
             }
+            CompilerError::InvalidIndex(index_type) => {
+                format!("index must be an integer, but got {index_type}")
+            }
+            CompilerError::NotIndexable(value_type) => {
+                format!("cannot index into a value of type {value_type}")
+            }
+            CompilerError::InvalidKey(key_type) => {
+                format!("map keys must be strings, but got {key_type}")
+            }
+            CompilerError::NotAssignable(value_type) => {
+                format!("cannot assign into a value of type {value_type}")
+            }
+            CompilerError::UndefinedKey(key) => format!("key '{key}' is not in the map"),
+            CompilerError::IndexOutOfRange(index, length) => {
+                format!("index {index} is out of range for a length of {length}")
+            }
+            CompilerError::InvalidRangeBound(bound_type) => {
+                format!("range bounds and steps must be integers, but got {bound_type}")
+            }
+            CompilerError::RangeStepZero => "the step of a range can not be 0".to_string(),
+            CompilerError::RangeTooLong(most) => {
+                format!("a range of more than {most} numbers is too long for a list")
+            }
+            CompilerError::NotIterable(value_type) => {
+                format!("cannot loop over a value of type {value_type}")
+            }
+            CompilerError::CallDepthExceeded(most) => {
+                format!("calls can not be nested more than {most} deep")
+            }
+            CompilerError::Located(error, span) => format!("{error} at {span}"),
+            CompilerError::Multiple(errors) => errors
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n"),
         };
         write!(f, "{}", text)
     }
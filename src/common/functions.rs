@@ -1,19 +1,25 @@
 use std::fmt::Display;
 use std::sync::{Arc, RwLock};
 
+use crate::common::errors::CompilerError;
+use crate::common::literal::Literal;
+use crate::evaluating::environment::Environment;
 use crate::parsing::ast::AbstractSyntaxTree;
 use crate::parsing::block::Block;
 use crate::parsing::seperated_statements::SeperatedStatements;
 
-#[derive(Debug)]
-pub(crate) struct Function {
+pub struct Function {
     pub(crate) block: Arc<RwLock<Block>>,
-    pub(crate) parameters: SeperatedStatements<AbstractSyntaxTree>,
+    pub(crate) parameters: Arc<SeperatedStatements<AbstractSyntaxTree>>,
+    // where the function was created, it stays alive as long as the function does.
+    // `None` only for the function literal in the syntax tree, before it is evaluated
+    pub(crate) environment: Option<Arc<Environment>>,
 }
 
+// a function is only ever equal to itself
 impl PartialEq for Function {
-    fn eq(&self, _: &Self) -> bool {
-        false
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
     }
 }
 
@@ -22,7 +28,31 @@ impl Function {
         block: Arc<RwLock<Block>>,
         parameters: SeperatedStatements<AbstractSyntaxTree>,
     ) -> Self {
-        Self { block, parameters }
+        Self {
+            block,
+            parameters: Arc::new(parameters),
+            environment: None,
+        }
+    }
+
+    // the closure made each time the literal is evaluated, sharing the variables
+    // of `environment` by reference instead of copying them
+    pub(crate) fn capture(&self, environment: Arc<Environment>) -> Self {
+        Self {
+            block: Arc::clone(&self.block),
+            parameters: Arc::clone(&self.parameters),
+            environment: Some(environment),
+        }
+    }
+}
+
+// the environment is left out, it can hold the function itself
+impl std::fmt::Debug for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Function")
+            .field("block", &self.block)
+            .field("parameters", &self.parameters)
+            .finish()
     }
 }
 
@@ -31,3 +61,36 @@ impl Display for Function {
         write!(f, "Function")
     }
 }
+
+/// A function that comes with the language, like `print`.
+#[derive(Clone, Copy)]
+pub struct BuiltInFunction {
+    pub(crate) name: &'static str,
+    pub(crate) function: fn(Vec<Literal>) -> Result<Literal, CompilerError>,
+}
+
+// every built in function has its own name
+impl PartialEq for BuiltInFunction {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl BuiltInFunction {
+    pub(crate) fn new(
+        name: &'static str,
+        function: fn(Vec<Literal>) -> Result<Literal, CompilerError>,
+    ) -> Self {
+        Self { name, function }
+    }
+
+    pub(crate) fn call(&self, arguements: Vec<Literal>) -> Result<Literal, CompilerError> {
+        (self.function)(arguements)
+    }
+}
+
+impl std::fmt::Debug for BuiltInFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BuiltInFunction({})", self.name)
+    }
+}
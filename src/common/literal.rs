@@ -1,4 +1,5 @@
-use std::{fmt::Display, sync::Arc};
+use std::fmt::Display;
+use std::sync::{Arc, RwLock};
 
 use super::{datatypes::DataType, errors::CompilerError};
 
@@ -13,6 +14,10 @@ impl Literal {
         Self { value, mutability }
     }
 
+    pub fn value(&self) -> &DataType {
+        &self.value
+    }
+
     pub(crate) fn is_truthy(&self) -> Result<bool, CompilerError> {
         let result = match &self.value {
             DataType::String(a) => !a.is_empty(),
@@ -20,8 +25,9 @@ impl Literal {
             DataType::Integer(a) => *a != 0,
             DataType::Boolean(a) => *a,
             DataType::Infinity => true,
-            DataType::Function(_) => true,
-            DataType::InternalUndefined => return Err(CompilerError::OperationOnUndefined),
+            DataType::Function(_) | DataType::BuiltIn(_) => true,
+            DataType::List(list) => !list.read().unwrap().is_empty(),
+            DataType::Map(map) => !map.read().unwrap().is_empty(),
             DataType::Return(_) => return Err(CompilerError::OperationOnReturn),
             DataType::Break(_) => return Err(CompilerError::OperationOnBreak),
             DataType::Skip(_) => return Err(CompilerError::OperationOnSkip),
@@ -29,25 +35,216 @@ impl Literal {
         Ok(result)
     }
 
+    // negative indices count from the end, maps are indexed by their keys
+    pub(crate) fn index(&self, index: &Literal) -> Result<Literal, CompilerError> {
+        match (&self.value, &index.value) {
+            (DataType::List(list), DataType::Integer(position)) => {
+                let list = list.read().unwrap();
+                let found = resolve_index(*position, list.len())?;
+                Ok(Literal::new(list[found].value.clone(), self.mutability))
+            }
+            (DataType::String(text), DataType::Integer(position)) => {
+                let length = text.chars().count();
+                let found = resolve_index(*position, length)?;
+                Ok(Literal::from(text.chars().nth(found).unwrap().to_string()))
+            }
+            (DataType::Map(_), DataType::String(key)) => self.member(key),
+            (DataType::Map(_), key) => Err(CompilerError::InvalidKey(key.to_string())),
+            (DataType::List(_) | DataType::String(_), index) => {
+                Err(CompilerError::InvalidIndex(index.to_string()))
+            }
+            (value, _) => Err(CompilerError::NotIndexable(value.to_string())),
+        }
+    }
+
+    // the value of `map.key`
+    pub(crate) fn member(&self, key: &str) -> Result<Literal, CompilerError> {
+        let DataType::Map(map) = &self.value else {
+            return Err(CompilerError::NotIndexable(self.value.to_string()));
+        };
+        map.read()
+            .unwrap()
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| Literal::new(value.value.clone(), self.mutability))
+            .ok_or_else(|| CompilerError::UndefinedKey(key.to_string()))
+    }
+
+    // replaces an item of a list, or the value of a key of a map
+    pub(crate) fn set_index(&self, index: &Literal, value: Literal) -> Result<(), CompilerError> {
+        match (&self.value, &index.value) {
+            (DataType::List(list), DataType::Integer(position)) => {
+                let mut list = list.write().unwrap();
+                let found = resolve_index(*position, list.len())?;
+                list[found] = value;
+                Ok(())
+            }
+            (DataType::Map(_), DataType::String(key)) => self.set_member(key, value),
+            (DataType::Map(_), key) => Err(CompilerError::InvalidKey(key.to_string())),
+            (DataType::List(_), index) => Err(CompilerError::InvalidIndex(index.to_string())),
+            (value, _) => Err(CompilerError::NotAssignable(value.to_string())),
+        }
+    }
+
+    // a new key goes after every key already in the map
+    pub(crate) fn set_member(&self, key: &str, value: Literal) -> Result<(), CompilerError> {
+        let DataType::Map(map) = &self.value else {
+            return Err(CompilerError::NotAssignable(self.value.to_string()));
+        };
+        let mut map = map.write().unwrap();
+        match map.iter_mut().find(|(name, _)| name == key) {
+            Some((_, old_value)) => *old_value = value,
+            None => map.push((key.to_string(), value)),
+        }
+        Ok(())
+    }
+
+    // what `loop for` goes over: the position and item of every item of a list
+    // or character of a string, or the key and value of every entry of a map.
+    // taken at the start of the loop, changes made by the loop are not seen
+    pub(crate) fn entries(&self) -> Result<Vec<(Literal, Literal)>, CompilerError> {
+        let entries = match &self.value {
+            DataType::List(list) => list
+                .read()
+                .unwrap()
+                .iter()
+                .enumerate()
+                .map(|(position, item)| {
+                    let item = Literal::new(item.value.clone(), self.mutability);
+                    (Literal::from(position as i128), item)
+                })
+                .collect(),
+            DataType::String(text) => text
+                .chars()
+                .enumerate()
+                .map(|(position, character)| {
+                    (
+                        Literal::from(position as i128),
+                        Literal::from(character.to_string()),
+                    )
+                })
+                .collect(),
+            DataType::Map(map) => map
+                .read()
+                .unwrap()
+                .iter()
+                .map(|(key, value)| {
+                    let value = Literal::new(value.value.clone(), self.mutability);
+                    (Literal::from(key.clone()), value)
+                })
+                .collect(),
+            value => return Err(CompilerError::NotIterable(value.to_string())),
+        };
+        Ok(entries)
+    }
+
     pub(crate) fn is_mutable(&self) -> bool {
         self.mutability
     }
 
-    pub(crate) fn new_mutable(value: DataType) -> Self {
+    pub(crate) fn to_mutable(&self) -> Self {
         Self {
-            value,
+            value: self.value.clone(),
             mutability: true,
         }
     }
 
-    pub(crate) fn to_mutable(&self) -> Self {
+    // values are shared until one of them changes, see `set_element`
+    pub(crate) fn into_immutable(self) -> Self {
         Self {
-            value: self.value.clone(),
-            mutability: true,
+            value: self.value,
+            mutability: false,
+        }
+    }
+
+    // the value at `element` of a list or map
+    pub(crate) fn element(&self, element: &Element) -> Result<Literal, CompilerError> {
+        match element {
+            Element::Index(index) => self.index(index),
+            Element::Member(key) => self.member(key),
+        }
+    }
+
+    // replaces the value at the end of `path`. a list or map on the way that is shared
+    // with other values is copied first, so that none of them sees the change
+    pub(crate) fn set_element(
+        &mut self,
+        path: &[Element],
+        value: Literal,
+    ) -> Result<(), CompilerError> {
+        self.unshare();
+        let (element, rest) = path.split_first().expect("an element to set");
+        if rest.is_empty() {
+            return match element {
+                Element::Index(index) => self.set_index(index, value),
+                Element::Member(key) => self.set_member(key, value),
+            };
+        }
+        let key = match element {
+            Element::Index(Literal {
+                value: DataType::String(key),
+                ..
+            }) => Some(key.as_str()),
+            Element::Member(key) => Some(key.as_str()),
+            Element::Index(_) => None,
+        };
+        match (&self.value, element, key) {
+            (
+                DataType::List(list),
+                Element::Index(Literal {
+                    value: DataType::Integer(position),
+                    ..
+                }),
+                _,
+            ) => {
+                let mut list = list.write().unwrap();
+                let found = resolve_index(*position, list.len())?;
+                list[found].set_element(rest, value)
+            }
+            (DataType::Map(map), _, Some(key)) => {
+                let mut map = map.write().unwrap();
+                match map.iter_mut().find(|(name, _)| name == key) {
+                    Some((_, old_value)) => old_value.set_element(rest, value),
+                    None => Err(CompilerError::UndefinedKey(key.to_string())),
+                }
+            }
+            // reading it gives the error, or the value that can not hold anything
+            _ => Err(CompilerError::NotAssignable(
+                self.element(element)?.value.to_string(),
+            )),
+        }
+    }
+
+    // `xs += ys` adds the items of `ys` to the list of `xs` itself
+    pub(crate) fn append(&mut self, items: &Literal) {
+        self.unshare();
+        if let (DataType::List(list), DataType::List(items)) = (&self.value, &items.value) {
+            let items = items.read().unwrap().clone();
+            list.write().unwrap().extend(items);
+        }
+    }
+
+    fn unshare(&mut self) {
+        match &mut self.value {
+            DataType::List(list) if Arc::strong_count(list) > 1 => {
+                let items = list.read().unwrap().clone();
+                *list = Arc::new(RwLock::new(items));
+            }
+            DataType::Map(map) if Arc::strong_count(map) > 1 => {
+                let entries = map.read().unwrap().clone();
+                *map = Arc::new(RwLock::new(entries));
+            }
+            _ => {}
         }
     }
 }
 
+// one step from a variable to the part of it that `xs[i] = v` or `user.name = v` changes
+pub(crate) enum Element {
+    Index(Literal),
+    Member(String),
+}
+
 impl From<Arc<String>> for Literal {
     fn from(value: Arc<String>) -> Self {
         Self {
@@ -90,6 +287,22 @@ impl From<f64> for Literal {
         }
     }
 }
+impl From<Vec<Literal>> for Literal {
+    fn from(value: Vec<Literal>) -> Self {
+        Self {
+            value: DataType::List(Arc::new(RwLock::new(value))),
+            mutability: false,
+        }
+    }
+}
+impl From<Vec<(String, Literal)>> for Literal {
+    fn from(value: Vec<(String, Literal)>) -> Self {
+        Self {
+            value: DataType::Map(Arc::new(RwLock::new(value))),
+            mutability: false,
+        }
+    }
+}
 impl From<DataType> for Literal {
     fn from(value: DataType) -> Self {
         Self {
@@ -107,8 +320,30 @@ impl Display for Literal {
             DataType::Integer(a) => a.to_string(),
             DataType::Boolean(a) => a.to_string(),
             DataType::Infinity => "Infinity".to_string(),
-            DataType::InternalUndefined => "Undefined".to_string(),
-            DataType::Function(_) => "Function".to_string(),
+            DataType::Function(_) | DataType::BuiltIn(_) => "Function".to_string(),
+            DataType::List(list) => {
+                let items: Vec<String> = list.read().unwrap().iter().map(quoted).collect();
+                format!("[{}]", items.join(", "))
+            }
+            DataType::Map(map) => {
+                let map = map.read().unwrap();
+                if map.is_empty() {
+                    "{}".to_string()
+                } else {
+                    let entries: Vec<String> = map
+                        .iter()
+                        .map(|(key, value)| {
+                            let is_name = key.chars().all(|ch| ch.is_alphanumeric() || ch == '_');
+                            if is_name && !key.is_empty() {
+                                format!("{key}: {}", quoted(value))
+                            } else {
+                                format!("{key:?}: {}", quoted(value))
+                            }
+                        })
+                        .collect();
+                    format!("{{ {} }}", entries.join(", "))
+                }
+            }
             DataType::Return(_) => "Return".to_string(),
             DataType::Break(_) => "Break".to_string(),
             DataType::Skip(_) => "skip".to_string(),
@@ -116,3 +351,23 @@ impl Display for Literal {
         write!(f, "{text}")
     }
 }
+
+pub(crate) fn resolve_index(index: i128, length: usize) -> Result<usize, CompilerError> {
+    let resolved = if index < 0 {
+        index + length as i128
+    } else {
+        index
+    };
+    if resolved < 0 || resolved >= length as i128 {
+        return Err(CompilerError::IndexOutOfRange(index, length));
+    }
+    Ok(resolved as usize)
+}
+
+// strings inside lists and maps are quoted, so that `["a, b"]` and `["a", "b"]` look different
+fn quoted(item: &Literal) -> String {
+    match &item.value {
+        DataType::String(text) => format!("{text:?}"),
+        _ => item.to_string(),
+    }
+}
@@ -3,3 +3,4 @@ pub(crate) mod errors;
 pub(crate) mod functions;
 pub(crate) mod literal;
 pub(crate) mod operators;
+pub(crate) mod span;
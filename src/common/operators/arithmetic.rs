@@ -53,7 +53,7 @@ impl Arithmetic {
                             )))
                         }
                     };
-                    if result.fract() == 0.0 {
+                    if result.fract() == 0.0 && result.abs() < i128::MAX as f64 {
                         Literal::from(result as i128)
                     } else {
                         Literal::from(result)
@@ -63,12 +63,11 @@ impl Arithmetic {
             },
             Subtraction => match variable.value {
                 Float(a) => Literal::from(-a),
-                Integer(a) => Literal::from(-a),
+                Integer(a) => checked(a.checked_neg(), Subtraction)?,
                 Boolean(a) => Literal::from(if a { -1 } else { 0 }),
                 Infinity => variable,
-                String(_) => return Err(CompilerError::InvalidUneryOperation),
-                InternalUndefined => return Err(CompilerError::OperationOnUndefined),
-                Function(_) => return Err(CompilerError::OperationOnFunction),
+                String(_) | List(_) | Map(_) => return Err(CompilerError::InvalidUneryOperation),
+                Function(_) | BuiltIn(_) => return Err(CompilerError::OperationOnFunction),
                 Return(_) => return Err(CompilerError::OperationOnReturn),
                 Break(_) => return Err(CompilerError::OperationOnBreak),
                 Skip(_) => return Err(CompilerError::OperationOnSkip),
@@ -97,12 +96,12 @@ impl Arithmetic {
 
             (Integer(a), String(b)) => Literal::from(Arc::new(format!("{a}{b}"))),
             (Integer(a), Float(b)) => Literal::from(a as f64 + b),
-            (Integer(a), Integer(b)) => Literal::from(a + b),
-            (Integer(a), Boolean(b)) => Literal::from(if b { a + 1 } else { a }),
+            (Integer(a), Integer(b)) => checked(a.checked_add(b), Addition)?,
+            (Integer(a), Boolean(b)) => checked(a.checked_add(b as i128), Addition)?,
 
             (Boolean(a), String(b)) => Literal::from(Arc::new(format!("{a}{b}"))),
             (Boolean(a), Float(b)) => Literal::from(if a { b + 1.0 } else { b }),
-            (Boolean(a), Integer(b)) => Literal::from(if a { b + 1 } else { b }),
+            (Boolean(a), Integer(b)) => checked(b.checked_add(a as i128), Addition)?,
             (Boolean(a), Boolean(b)) => Literal::from(match (a, b) {
                 (true, true) => 2,
                 (false, false) => 0,
@@ -112,11 +111,22 @@ impl Arithmetic {
             (_, Return(_)) | (Return(_), _) => return Err(CompilerError::OperationOnReturn),
             (_, Break(_)) | (Break(_), _) => return Err(CompilerError::OperationOnBreak),
             (_, Skip(_)) | (Skip(_), _) => return Err(CompilerError::OperationOnSkip),
-            (_, Function(_)) | (Function(_), _) => return Err(CompilerError::OperationOnFunction),
-            (_, Infinity) | (Infinity, _) => Literal::from(Infinity),
-            (_, InternalUndefined) | (InternalUndefined, _) => {
-                return Err(CompilerError::OperationOnUndefined)
+            (_, Function(_) | BuiltIn(_)) | (Function(_) | BuiltIn(_), _) => {
+                return Err(CompilerError::OperationOnFunction)
+            }
+            (List(a), List(b)) => {
+                let mut items = a.read().unwrap().clone();
+                items.extend(b.read().unwrap().iter().cloned());
+                Literal::from(items)
+            }
+            (left @ (List(_) | Map(_)), right) | (left, right @ (List(_) | Map(_))) => {
+                return Err(CompilerError::UnsupportedOperationBetween(
+                    Literal::from(left),
+                    Operator::Arithmetic(Addition),
+                    Literal::from(right),
+                ))
             }
+            (_, Infinity) | (Infinity, _) => Literal::from(Infinity),
         };
         Ok(result)
     }
@@ -136,10 +146,10 @@ impl Arithmetic {
             (Float(a), Boolean(b)) => Literal::from(if b { a - 1.0 } else { a }),
 
             (Integer(a), Float(b)) => Literal::from(a as f64 - b),
-            (Integer(a), Integer(b)) => Literal::from(a - b),
-            (Integer(a), Boolean(b)) => Literal::from(if b { a - 1 } else { a }),
+            (Integer(a), Integer(b)) => checked(a.checked_sub(b), Subtraction)?,
+            (Integer(a), Boolean(b)) => checked(a.checked_sub(b as i128), Subtraction)?,
             (Boolean(a), Float(b)) => Literal::from(if a { b - 1.0 } else { b }),
-            (Boolean(a), Integer(b)) => Literal::from(if a { b - 1 } else { b }),
+            (Boolean(a), Integer(b)) => checked((a as i128).checked_sub(b), Subtraction)?,
             (Boolean(a), Boolean(b)) => Literal::from(match (a, b) {
                 (true, false) => 1,
                 (false, true) => -1,
@@ -148,11 +158,17 @@ impl Arithmetic {
             (_, Return(_)) | (Return(_), _) => return Err(CompilerError::OperationOnReturn),
             (_, Break(_)) | (Break(_), _) => return Err(CompilerError::OperationOnBreak),
             (_, Skip(_)) | (Skip(_), _) => return Err(CompilerError::OperationOnSkip),
-            (_, Function(_)) | (Function(_), _) => return Err(CompilerError::OperationOnFunction),
-            (Infinity, _) | (_, Infinity) => Literal::from(Infinity),
-            (_, InternalUndefined) | (InternalUndefined, _) => {
-                return Err(CompilerError::OperationOnUndefined)
+            (_, Function(_) | BuiltIn(_)) | (Function(_) | BuiltIn(_), _) => {
+                return Err(CompilerError::OperationOnFunction)
             }
+            (left @ (List(_) | Map(_)), right) | (left, right @ (List(_) | Map(_))) => {
+                return Err(CompilerError::UnsupportedOperationBetween(
+                    Literal::from(left),
+                    Operator::Arithmetic(Subtraction),
+                    Literal::from(right),
+                ))
+            }
+            (Infinity, _) | (_, Infinity) => Literal::from(Infinity),
         };
         Ok(result)
     }
@@ -199,7 +215,7 @@ impl Arithmetic {
                 Literal::from(Arc::new(result))
             }
             (Integer(a), Float(b)) => Literal::from(a as f64 * b),
-            (Integer(a), Integer(b)) => Literal::from(a * b),
+            (Integer(a), Integer(b)) => checked(a.checked_mul(b), Multiplication)?,
 
             (Integer(a), Boolean(b)) => Literal::from(if b { a } else { 0 }),
             (Boolean(a), String(b)) => Literal::from(if a { b } else { Arc::new("".to_string()) }),
@@ -224,11 +240,17 @@ impl Arithmetic {
             (_, Return(_)) | (Return(_), _) => return Err(CompilerError::OperationOnReturn),
             (_, Break(_)) | (Break(_), _) => return Err(CompilerError::OperationOnBreak),
             (_, Skip(_)) | (Skip(_), _) => return Err(CompilerError::OperationOnSkip),
-            (_, Function(_)) | (Function(_), _) => return Err(CompilerError::OperationOnFunction),
-            (Infinity, _) | (_, Infinity) => Literal::from(Infinity),
-            (_, InternalUndefined) | (InternalUndefined, _) => {
-                return Err(CompilerError::OperationOnUndefined)
+            (_, Function(_) | BuiltIn(_)) | (Function(_) | BuiltIn(_), _) => {
+                return Err(CompilerError::OperationOnFunction)
+            }
+            (left @ (List(_) | Map(_)), right) | (left, right @ (List(_) | Map(_))) => {
+                return Err(CompilerError::UnsupportedOperationBetween(
+                    Literal::from(left),
+                    Operator::Arithmetic(Multiplication),
+                    Literal::from(right),
+                ))
             }
+            (Infinity, _) | (_, Infinity) => Literal::from(Infinity),
         };
         Ok(result)
     }
@@ -299,7 +321,7 @@ impl Arithmetic {
                 if b == 0 && a == 0 {
                     return Err(CompilerError::MathUndefined);
                 } else if b == 0 {
-                    Literal::from(a)
+                    Literal::from(Infinity)
                 } else {
                     Literal::from(a as f64 / b as f64)
                 }
@@ -347,17 +369,23 @@ impl Arithmetic {
                 if a == 0 {
                     return Err(CompilerError::MathUndefined);
                 } else {
-                    Literal::from(a)
+                    Literal::from(Infinity)
                 }
             }
             (_, Return(_)) | (Return(_), _) => return Err(CompilerError::OperationOnReturn),
             (_, Break(_)) | (Break(_), _) => return Err(CompilerError::OperationOnBreak),
             (_, Skip(_)) | (Skip(_), _) => return Err(CompilerError::OperationOnSkip),
-            (_, Function(_)) | (Function(_), _) => return Err(CompilerError::OperationOnFunction),
-            (Infinity, Infinity) => return Err(CompilerError::MathUndefined),
-            (_, InternalUndefined) | (InternalUndefined, _) => {
-                return Err(CompilerError::OperationOnUndefined)
+            (_, Function(_) | BuiltIn(_)) | (Function(_) | BuiltIn(_), _) => {
+                return Err(CompilerError::OperationOnFunction)
             }
+            (left @ (List(_) | Map(_)), right) | (left, right @ (List(_) | Map(_))) => {
+                return Err(CompilerError::UnsupportedOperationBetween(
+                    Literal::from(left),
+                    Operator::Arithmetic(Division),
+                    Literal::from(right),
+                ))
+            }
+            (Infinity, Infinity) => return Err(CompilerError::MathUndefined),
         };
         Ok(result)
     }
@@ -435,7 +463,7 @@ impl Arithmetic {
                 } else if b == 0 {
                     Literal::from(Infinity)
                 } else {
-                    Literal::from(a % b)
+                    checked(a.checked_rem(b), Modulo)?
                 }
             }
             (Boolean(a), Float(b)) => {
@@ -479,9 +507,15 @@ impl Arithmetic {
             (_, Return(_)) | (Return(_), _) => return Err(CompilerError::OperationOnReturn),
             (_, Break(_)) | (Break(_), _) => return Err(CompilerError::OperationOnBreak),
             (_, Skip(_)) | (Skip(_), _) => return Err(CompilerError::OperationOnSkip),
-            (_, Function(_)) | (Function(_), _) => return Err(CompilerError::OperationOnFunction),
-            (_, InternalUndefined) | (InternalUndefined, _) => {
-                return Err(CompilerError::OperationOnUndefined)
+            (_, Function(_) | BuiltIn(_)) | (Function(_) | BuiltIn(_), _) => {
+                return Err(CompilerError::OperationOnFunction)
+            }
+            (left @ (List(_) | Map(_)), right) | (left, right @ (List(_) | Map(_))) => {
+                return Err(CompilerError::UnsupportedOperationBetween(
+                    Literal::from(left),
+                    Operator::Arithmetic(Modulo),
+                    Literal::from(right),
+                ))
             }
         };
         Ok(result)
@@ -541,15 +575,16 @@ impl Arithmetic {
             (Integer(a), Integer(b)) => {
                 if b == 0 && a == 0 {
                     return Err(CompilerError::MathUndefined);
-                } else if b == 0 {
-                    Literal::from(1)
-                } else {
+                } else if b < 0 {
+                    // a fraction, unless the base is 1 or -1
                     let result = (a as f64).powf(b as f64);
                     if result.fract() == 0.0 {
                         Literal::from(result as i128)
                     } else {
                         Literal::from(result)
                     }
+                } else {
+                    checked(integer_power(a, b), Exponentiation)?
                 }
             }
             (Boolean(a), Float(b)) => {
@@ -586,11 +621,35 @@ impl Arithmetic {
             (_, Return(_)) | (Return(_), _) => return Err(CompilerError::OperationOnReturn),
             (_, Break(_)) | (Break(_), _) => return Err(CompilerError::OperationOnBreak),
             (_, Skip(_)) | (Skip(_), _) => return Err(CompilerError::OperationOnSkip),
-            (_, Function(_)) | (Function(_), _) => return Err(CompilerError::OperationOnFunction),
-            (_, InternalUndefined) | (InternalUndefined, _) => {
-                return Err(CompilerError::OperationOnUndefined)
+            (_, Function(_) | BuiltIn(_)) | (Function(_) | BuiltIn(_), _) => {
+                return Err(CompilerError::OperationOnFunction)
+            }
+            (left @ (List(_) | Map(_)), right) | (left, right @ (List(_) | Map(_))) => {
+                return Err(CompilerError::UnsupportedOperationBetween(
+                    Literal::from(left),
+                    Operator::Arithmetic(Exponentiation),
+                    Literal::from(right),
+                ))
             }
         };
         Ok(result)
     }
 }
+
+// the result of integer arithmetic, or an error when it does not fit in an integer
+fn checked(result: Option<i128>, operator: Arithmetic) -> Result<Literal, CompilerError> {
+    result
+        .map(Literal::from)
+        .ok_or(CompilerError::IntegerOverflow(Operator::Arithmetic(
+            operator,
+        )))
+}
+
+// `base ** exponent` for an exponent that is not negative
+fn integer_power(base: i128, exponent: i128) -> Option<i128> {
+    match base {
+        0 | 1 => Some(base),
+        -1 => Some(if exponent % 2 == 0 { 1 } else { -1 }),
+        _ => base.checked_pow(u32::try_from(exponent).ok()?),
+    }
+}
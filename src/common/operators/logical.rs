@@ -1,6 +1,5 @@
 use std::fmt::Display;
 
-use crate::common::datatypes::DataType::*;
 use crate::common::errors::CompilerError;
 use crate::common::literal::Literal;
 
@@ -49,18 +48,7 @@ impl Logical {
 
     pub(crate) fn evaluate_unary(&self, variable: Literal) -> Result<Literal, CompilerError> {
         let result = match self {
-            Logical::Not => match variable.value {
-                Boolean(value) => Literal::from(!value),
-                String(value) => Literal::from(value.len() > 0),
-                Float(value) => Literal::from(value != 0.0),
-                Integer(value) => Literal::from(value != 0),
-                Infinity => Literal::from(Infinity),
-                InternalUndefined => return Err(CompilerError::OperationOnUndefined),
-                Function(_) => return Err(CompilerError::OperationOnFunction),
-                Return(_) => return Err(CompilerError::OperationOnReturn),
-                Break(_) => return Err(CompilerError::OperationOnBreak),
-                Skip(_) => return Err(CompilerError::OperationOnSkip),
-            },
+            Logical::Not => Literal::from(!variable.is_truthy()?),
             _ => Literal::from(false),
         };
         Ok(result)
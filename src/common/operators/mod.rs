@@ -29,54 +29,69 @@ impl Operator {
             Operator::Assignment(_) => todo!(),
         }
     }
-    pub(crate) fn get_binary_precedence(&self) -> u8 {
-        match self {
-            Operator::Arithmetic(operator) => match operator {
-                Arithmetic::Addition => 6,
-                Arithmetic::Subtraction => 6,
-                Arithmetic::Multiplication => 7,
-                Arithmetic::Division => 7,
-                Arithmetic::Modulo => 7,
-                Arithmetic::Exponentiation => 8,
-            },
+
+    /// How tightly the operator holds its operands when it stands between two expressions,
+    /// `None` for operators that are never binary.
+    ///
+    /// | power | operators                       | associativity |
+    /// |-------|---------------------------------|---------------|
+    /// | 1     | `or`                            | left          |
+    /// | 2     | `xor`                           | left          |
+    /// | 3     | `and`                           | left          |
+    /// | 4     | `not` (prefix)                  |               |
+    /// | 5     | `==` `!=` `is` `is not`         | left          |
+    /// | 6     | `<` `<=` `>` `>=`               | left          |
+    /// | 7     | `+` `-`                         | left          |
+    /// | 8     | `*` `/` `%`                     | left          |
+    /// | 9     | `+` `-` (prefix)                |               |
+    /// | 10    | `**`                            | right         |
+    ///
+    /// so `2 ** 3 ** 2` is `2 ** (3 ** 2)`, `-2 ** 2` is `-(2 ** 2)`
+    /// and `not a == b` is `not (a == b)`.
+    pub(crate) fn binary_binding_power(&self) -> Option<(u8, Associativity)> {
+        let power = match self {
+            Operator::Logical(Logical::Or) => 1,
+            Operator::Logical(Logical::Xor) => 2,
+            Operator::Logical(Logical::And) => 3,
             Operator::Relational(operator) => match operator {
-                Relational::Equality => 4,
-                Relational::InEquality => 4,
-                Relational::LessThan => 5,
-                Relational::LessThanOrEquals => 5,
-                Relational::GreaterThan => 5,
-                Relational::GreaterThanOrEquals => 5,
+                Relational::Equality
+                | Relational::InEquality
+                | Relational::Identity
+                | Relational::NonIdentity => 5,
+                Relational::LessThan
+                | Relational::LessThanOrEquals
+                | Relational::GreaterThan
+                | Relational::GreaterThanOrEquals => 6,
             },
-            Operator::Assignment(operator) => match operator {
-                Assingment::Simple => 0,
-                Assingment::Addition => 0,
-                Assingment::Subtraction => 0,
-                Assingment::Multiplication => 0,
-                Assingment::Division => 0,
-                Assingment::Modulo => 0,
-                Assingment::Exponentiation => 0,
-            },
-            Operator::Logical(operator) => match operator {
-                Logical::And => 3,
-                Logical::Or => 3,
-                Logical::Not => 8,
-                Logical::Xor => 1,
+            Operator::Arithmetic(operator) => match operator {
+                Arithmetic::Addition | Arithmetic::Subtraction => 7,
+                Arithmetic::Multiplication | Arithmetic::Division | Arithmetic::Modulo => 8,
+                Arithmetic::Exponentiation => return Some((10, Associativity::Right)),
             },
-        }
+            Operator::Logical(Logical::Not) | Operator::Assignment(_) => return None,
+        };
+        Some((power, Associativity::Left))
     }
-    pub(crate) fn get_unery_precedence(&self) -> u8 {
+
+    /// How tightly the operator holds its operand when it comes before it,
+    /// `None` for operators that are never unary. see [`Operator::binary_binding_power`].
+    pub(crate) fn prefix_binding_power(&self) -> Option<u8> {
         match self {
-            Operator::Arithmetic(operator) => match operator {
-                Arithmetic::Addition => 9,
-                Arithmetic::Subtraction => 9,
-                _ => 0,
-            },
-            Operator::Logical(Logical::Not) => 9,
-            _ => 0,
+            Operator::Logical(Logical::Not) => Some(4),
+            Operator::Arithmetic(Arithmetic::Addition | Arithmetic::Subtraction) => Some(9),
+            _ => None,
         }
     }
 }
 
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum Associativity {
+    // `a - b - c` is `(a - b) - c`
+    Left,
+    // `a ** b ** c` is `a ** (b ** c)`
+    Right,
+}
+
 impl Display for Operator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
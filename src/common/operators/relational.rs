@@ -1,5 +1,6 @@
-use crate::common::datatypes::DataType::*;
+use crate::common::datatypes::{compare_integer_to_float, DataType, DataType::*};
 use crate::common::literal::Literal;
+use std::cmp::Ordering::{self, *};
 use std::fmt::Display;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -10,6 +11,8 @@ pub enum Relational {
     LessThanOrEquals,
     GreaterThan,
     GreaterThanOrEquals,
+    Identity,
+    NonIdentity,
 }
 
 impl Display for Relational {
@@ -21,6 +24,8 @@ impl Display for Relational {
             Relational::LessThanOrEquals => "<=",
             Relational::GreaterThan => ">",
             Relational::GreaterThanOrEquals => ">=",
+            Relational::Identity => "is",
+            Relational::NonIdentity => "is not",
         };
         write!(f, "{}", text)
     }
@@ -29,29 +34,17 @@ impl Display for Relational {
 impl Relational {
     pub(crate) fn evaluate(&self, a: Literal, b: Literal) -> Literal {
         let result = match self {
-            Relational::Equality => match (a.value, b.value) {
-                (String(a), String(b)) => Boolean(a == b),
-                (Float(a), Float(b)) => Boolean(a == b),
-                (Integer(a), Integer(b)) => Boolean(a == b),
-                (Boolean(a), Boolean(b)) => Boolean(a == b),
-                (Infinity, Infinity) => Boolean(true),
-                _ => Boolean(false),
-            },
-            Relational::InEquality => match (a.value, b.value) {
-                (String(a), String(b)) => Boolean(a != b),
-                (Float(a), Float(b)) => Boolean(a != b),
-                (Integer(a), Integer(b)) => Boolean(a != b),
-                (Boolean(a), Boolean(b)) => Boolean(a != b),
-                (Infinity, Infinity) => Boolean(false),
-                _ => Boolean(true),
-            },
+            Relational::Equality => Boolean(a.value == b.value),
+            Relational::InEquality => Boolean(a.value != b.value),
+            Relational::Identity => Boolean(a.value.is_identical(&b.value)),
+            Relational::NonIdentity => Boolean(!a.value.is_identical(&b.value)),
             Relational::LessThan => match (a.value, b.value) {
                 (String(a), String(b)) => Boolean(a < b),
                 (Float(a), Float(b)) => Boolean(a < b),
                 (Integer(a), Integer(b)) => Boolean(a < b),
                 (Boolean(a), Boolean(b)) => Boolean(!a & b),
                 (Infinity, Infinity) => Boolean(false),
-                _ => Boolean(false),
+                (a, b) => Boolean(matches!(mixed_order(&a, &b), Some(Less))),
             },
             Relational::LessThanOrEquals => match (a.value, b.value) {
                 (String(a), String(b)) => Boolean(a <= b),
@@ -59,7 +52,7 @@ impl Relational {
                 (Integer(a), Integer(b)) => Boolean(a <= b),
                 (Boolean(a), Boolean(b)) => Boolean(a <= b),
                 (Infinity, Infinity) => Boolean(true),
-                _ => Boolean(false),
+                (a, b) => Boolean(matches!(mixed_order(&a, &b), Some(Less | Equal))),
             },
             Relational::GreaterThan => match (a.value, b.value) {
                 (String(a), String(b)) => Boolean(a > b),
@@ -67,7 +60,7 @@ impl Relational {
                 (Integer(a), Integer(b)) => Boolean(a > b),
                 (Boolean(a), Boolean(b)) => Boolean(a & !b),
                 (Infinity, Infinity) => Boolean(false),
-                _ => Boolean(false),
+                (a, b) => Boolean(matches!(mixed_order(&a, &b), Some(Greater))),
             },
             Relational::GreaterThanOrEquals => match (a.value, b.value) {
                 (String(a), String(b)) => Boolean(a >= b),
@@ -75,10 +68,19 @@ impl Relational {
                 (Integer(a), Integer(b)) => Boolean(a >= b),
                 (Boolean(a), Boolean(b)) => Boolean(a >= b),
                 (Infinity, Infinity) => Boolean(true),
-                _ => Boolean(false),
+                (a, b) => Boolean(matches!(mixed_order(&a, &b), Some(Greater | Equal))),
             },
         };
 
         Literal::from(result)
     }
 }
+
+// the order of an integer and a float, `None` for any other values
+fn mixed_order(a: &DataType, b: &DataType) -> Option<Ordering> {
+    match (a, b) {
+        (Integer(a), Float(b)) => compare_integer_to_float(*a, *b),
+        (Float(a), Integer(b)) => compare_integer_to_float(*b, *a).map(Ordering::reverse),
+        _ => None,
+    }
+}
@@ -0,0 +1,38 @@
+use std::fmt::Display;
+
+// a range of source code. lines and columns start at 1, columns count characters.
+// every token and node carries one, so it is kept to 32 bits a field
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Span {
+    pub file_id: u32,
+    pub start_byte: u32,
+    pub end_byte: u32,
+    pub line: u32,
+    pub col: u32,
+}
+
+impl Span {
+    pub(crate) fn new(file_id: u32, start_byte: u32, end_byte: u32, line: u32, col: u32) -> Self {
+        Self {
+            file_id,
+            start_byte,
+            end_byte,
+            line,
+            col,
+        }
+    }
+
+    // from the start of this span to the end of the other
+    pub(crate) fn to(self, other: Span) -> Self {
+        Self {
+            end_byte: other.end_byte,
+            ..self
+        }
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.col)
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+
+use crate::common::datatypes::DataType;
+use crate::common::functions::Function;
+use crate::common::literal::Literal;
+use crate::parsing::symbol_table::SymbolTable;
+
+// the variables of one run of a block, a loop body or a function call.
+// a `Block` is only the parsed code, so every run gets a fresh environment
+// and a recursive call never sees the variables of the call that made it
+#[derive(Default)]
+pub(crate) struct Environment {
+    pub(crate) parent: Option<Arc<Environment>>,
+    symbols: Mutex<SymbolTable>,
+}
+
+impl Environment {
+    pub(crate) fn new() -> Self {
+        Self {
+            parent: None,
+            symbols: Mutex::new(SymbolTable::new()),
+        }
+    }
+
+    // updates the variable where it was defined, or defines it here
+    pub(crate) fn add_symbol(&self, name: String, value: Literal) {
+        if self
+            .update_parent_symbol(name.clone(), value.clone())
+            .is_none()
+        {
+            self.define_symbol(name, value);
+        }
+    }
+
+    // defines the variable here, even when an enclosing environment has one with the same name
+    pub(crate) fn define_symbol(&self, name: String, value: Literal) {
+        self.symbols.lock().unwrap().add(name, value);
+    }
+
+    fn update_parent_symbol(&self, name: String, value: Literal) -> Option<()> {
+        let mut symbols = self.symbols.lock().unwrap();
+        if symbols.contains(&name) {
+            symbols.add(name, value);
+            Some(())
+        } else if let Some(parent) = self.parent.as_ref() {
+            drop(symbols);
+            parent.update_parent_symbol(name, value)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn contains_symbol(&self, name: &str) -> bool {
+        self.get_symbol(name).is_some()
+    }
+
+    pub(crate) fn get_symbol(&self, name: &str) -> Option<Literal> {
+        if let Some(variable) = self.symbols.lock().unwrap().get(name) {
+            Some(variable)
+        } else if let Some(parent) = self.parent.as_ref() {
+            parent.get_symbol(name)
+        } else {
+            None
+        }
+    }
+
+    // changes the variable in place, where it was defined
+    pub(crate) fn update_symbol<R>(
+        &self,
+        name: &str,
+        update: impl FnOnce(&mut Literal) -> R,
+    ) -> Option<R> {
+        let mut symbols = self.symbols.lock().unwrap();
+        if let Some(variable) = symbols.get_mut(name) {
+            Some(update(variable))
+        } else if let Some(parent) = self.parent.as_ref() {
+            drop(symbols);
+            parent.update_symbol(name, update)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn symbol_table(&self) -> MutexGuard<'_, SymbolTable> {
+        self.symbols.lock().unwrap()
+    }
+
+    // called with the last handle of a run when it ends. a function kept in the environment
+    // it was created in holds on to that environment, which holds on to the function,
+    // so neither would ever be freed. when nothing else uses them, the variables are dropped.
+    // returns the environment when it has to be tried again later
+    pub(crate) fn release(self: Arc<Self>) -> Option<Weak<Environment>> {
+        // no function holds on to it, it goes with this handle
+        if Arc::strong_count(&self) == 1 {
+            return None;
+        }
+        let mut symbols = self.symbols.lock().unwrap();
+        let mut reached = Reached::default();
+        for value in symbols.values() {
+            reached.visit(value);
+        }
+        // this environment and the ones between it and the functions, by address, with the
+        // references to them that the functions account for. `self` is one of them
+        let mut environments = HashMap::from([(Arc::as_ptr(&self), (&self, 1))]);
+        for function in reached.functions.iter() {
+            let Some(mut environment) = function.environment.as_ref() else {
+                continue;
+            };
+            if !environment.is_within(&self) {
+                continue;
+            }
+            loop {
+                match environments.entry(Arc::as_ptr(environment)) {
+                    Entry::Occupied(mut known) => {
+                        known.get_mut().1 += 1;
+                        break;
+                    }
+                    Entry::Vacant(new) => {
+                        new.insert((environment, 1));
+                        // `is_within` found `self` among the parents
+                        environment = environment.parent.as_ref().unwrap();
+                    }
+                }
+            }
+        }
+        if environments.len() == 1 && environments[&Arc::as_ptr(&self)].1 == 1 {
+            // none of its functions holds on to it
+            return None;
+        }
+        let unused = reached.is_unused()
+            && environments
+                .values()
+                .all(|(environment, references)| Arc::strong_count(environment) == *references);
+        drop(environments);
+        if unused {
+            symbols.clear();
+            None
+        } else {
+            drop(symbols);
+            Some(Arc::downgrade(&self))
+        }
+    }
+
+    fn is_within(self: &Arc<Self>, other: &Arc<Environment>) -> bool {
+        let mut environment = Some(self);
+        while let Some(current) = environment {
+            if Arc::ptr_eq(current, other) {
+                return true;
+            }
+            environment = current.parent.as_ref();
+        }
+        false
+    }
+}
+
+impl From<Arc<Environment>> for Environment {
+    fn from(parent: Arc<Environment>) -> Self {
+        Self {
+            parent: Some(parent),
+            symbols: Mutex::new(SymbolTable::new()),
+        }
+    }
+}
+
+// the functions, lists and maps reached from the variables of an environment
+#[derive(Default)]
+struct Reached {
+    // how many handles every value has and how many of them were reached, by address
+    values: HashMap<*const (), (usize, usize)>,
+    functions: Vec<Arc<Function>>,
+}
+
+impl Reached {
+    // returns whether the value is reached for the first time
+    fn reach<T>(&mut self, value: &Arc<T>) -> bool {
+        match self.values.entry(Arc::as_ptr(value) as *const ()) {
+            Entry::Occupied(mut known) => {
+                known.get_mut().1 += 1;
+                false
+            }
+            Entry::Vacant(new) => {
+                new.insert((Arc::strong_count(value), 1));
+                true
+            }
+        }
+    }
+
+    fn visit(&mut self, value: &Literal) {
+        match &value.value {
+            DataType::Function(function) if self.reach(function) => {
+                self.functions.push(Arc::clone(function));
+            }
+            DataType::List(list) if self.reach(list) => {
+                for item in list.read().unwrap().iter() {
+                    self.visit(item);
+                }
+            }
+            DataType::Map(map) if self.reach(map) => {
+                for (_, value) in map.read().unwrap().iter() {
+                    self.visit(value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // whether every handle of every value was reached, so nothing else uses them
+    fn is_unused(&self) -> bool {
+        self.values
+            .values()
+            .all(|(handles, reached)| handles == reached)
+    }
+}
+
+// the environments whose variables were still in use when their run ended. the value
+// of the run often holds one of their functions for a little while, so they are tried
+// again every time there are twice as many of them as after the last try
+#[derive(Default)]
+pub(crate) struct PendingReleases {
+    environments: Vec<Weak<Environment>>,
+    limit: usize,
+}
+
+impl PendingReleases {
+    pub(crate) fn release(&mut self, environment: Arc<Environment>) {
+        let Some(pending) = environment.release() else {
+            return;
+        };
+        self.environments.push(pending);
+        if self.environments.len() > self.limit {
+            for pending in std::mem::take(&mut self.environments) {
+                if let Some(pending) = pending.upgrade().and_then(Environment::release) {
+                    self.environments.push(pending);
+                }
+            }
+            self.limit = (self.environments.len() * 2).max(64);
+        }
+    }
+}
@@ -1,9 +1,11 @@
+use std::cell::Cell;
 use std::sync::mpsc::Receiver;
 use std::sync::{Arc, RwLock};
 
 use crate::common::datatypes::DataType;
 use crate::common::errors::CompilerError;
-use crate::common::literal::Literal;
+use crate::common::functions::Function;
+use crate::common::literal::{Element, Literal};
 use crate::common::operators::arithmetic::Arithmetic::*;
 use crate::common::operators::assignment::Assingment;
 use crate::common::operators::logical::Logical::Not;
@@ -14,44 +16,74 @@ use crate::parsing::ast::AbstractSyntaxTree;
 use crate::parsing::block::Block;
 use crate::parsing::seperated_statements::SeperatedStatements;
 
+use super::environment::Environment;
 use super::global::Global;
+use super::range::{Range, MAX_LIST_LENGTH};
+
+// the stack of the evaluator thread fits this many calls, even of functions
+// that nest loops and blocks, so that deep recursion fails instead of aborting
+const MAX_CALL_DEPTH: usize = 1000;
 
 pub(crate) struct Evaluator {
     statement_receiver: Receiver<AbstractSyntaxTree>,
-    global: Global,
+    global: Arc<Global>,
+    // how many calls of prolang functions are running
+    depth: Cell<usize>,
 }
 
 impl Evaluator {
-    pub(crate) fn new(statement_receiver: Receiver<AbstractSyntaxTree>, global: Global) -> Self {
+    pub(crate) fn new(
+        statement_receiver: Receiver<AbstractSyntaxTree>,
+        global: Arc<Global>,
+    ) -> Self {
         Self {
             global,
             statement_receiver,
+            depth: Cell::new(0),
         }
     }
 
-    pub(crate) fn evaluate(&self) {
+    /// evaluates every statement in order and returns the value of the last one.
+    /// stops at the first runtime error.
+    pub(crate) fn evaluate(&self) -> Result<Literal, CompilerError> {
+        let mut result = Literal::from(false);
         for statement in &self.statement_receiver {
-            if let Err(err) = self.evaluate_statement(&statement, Arc::clone(&self.global.block)) {
-                eprintln!("{err}");
-            }
+            result = self.evaluate_statement(&statement, &self.global.environment)?;
         }
+        Ok(result)
     }
 
+    // runtime errors are pointed at the innermost statement that raised them
     fn evaluate_statement(
         &self,
         statement: &AbstractSyntaxTree,
-        block: Arc<RwLock<Block>>,
+        environment: &Arc<Environment>,
+    ) -> Result<Literal, CompilerError> {
+        self.evaluate_node(statement, environment)
+            .map_err(|err| err.at(statement.span()))
+    }
+
+    fn evaluate_node(
+        &self,
+        statement: &AbstractSyntaxTree,
+        environment: &Arc<Environment>,
     ) -> Result<Literal, CompilerError> {
         match statement {
-            AbstractSyntaxTree::Literal(literal) => Ok(literal.clone()),
-            AbstractSyntaxTree::BinaryExpression(left, operator, right) => {
-                self.evaluate_binary_expression(left, block, right, operator)
+            // a function literal becomes a closure over the environment it is evaluated in
+            AbstractSyntaxTree::Literal(literal, _) => match &literal.value {
+                DataType::Function(function) => Ok(Literal::from(DataType::Function(Arc::new(
+                    function.capture(Arc::clone(environment)),
+                )))),
+                _ => Ok(literal.clone()),
+            },
+            AbstractSyntaxTree::BinaryExpression(left, operator, right, _) => {
+                self.evaluate_binary_expression(left, environment, right, operator)
             }
-            AbstractSyntaxTree::UnaryExpression(operator, expression) => {
-                self.evaluate_unary_expression(operator, expression, block)
+            AbstractSyntaxTree::UnaryExpression(operator, expression, _) => {
+                self.evaluate_unary_expression(operator, expression, environment)
             }
-            AbstractSyntaxTree::Identifier(name) => {
-                if let Some(value) = block.read().unwrap().get_symbol(name) {
+            AbstractSyntaxTree::Identifier(name, _) => {
+                if let Some(value) = environment.get_symbol(name) {
                     Ok(value)
                 } else if let Some(variable) = self.global.get_built_in_properties(name) {
                     Ok(variable)
@@ -59,62 +91,121 @@ impl Evaluator {
                     Err(CompilerError::UndefinedVariable(name.clone()))
                 }
             }
-            AbstractSyntaxTree::AssignmentExpression(name, operator, expression) => {
-                self.evaluate_assignment_expression(name, operator, expression, block)
-            }
-            AbstractSyntaxTree::ParenthesizedExpression(expression) => {
-                self.evaluate_statement(expression, block)
+            AbstractSyntaxTree::InterpolatedString(parts, _) => {
+                let mut text = String::new();
+                for part in parts {
+                    let value = self.evaluate_statement(part, environment)?;
+                    text.push_str(&value.to_string());
+                }
+                Ok(Literal::from(text))
             }
-            AbstractSyntaxTree::BlockStatement(block_statement) => {
-                self.evaluate_block(Arc::clone(block_statement))
+            AbstractSyntaxTree::ListExpression(items, _) => {
+                let mut list = Vec::with_capacity(items.len());
+                for item in items.iter() {
+                    list.push(self.evaluate_statement(item, environment)?.into_immutable());
+                }
+                Ok(Literal::from(list))
             }
-            AbstractSyntaxTree::IfStatement(condition, if_block_or_statement, else_statement) => {
-                self.evaluate_if_statement(condition, if_block_or_statement, else_statement, block)
+            AbstractSyntaxTree::MapExpression(entries, _) => {
+                let mut map: Vec<(String, Literal)> = Vec::with_capacity(entries.len());
+                for entry in entries.iter() {
+                    let value = self
+                        .evaluate_statement(&entry.value, environment)?
+                        .into_immutable();
+                    // a repeated key keeps its first place and takes the last value
+                    match map.iter_mut().find(|(key, _)| *key == entry.key) {
+                        Some((_, old_value)) => *old_value = value,
+                        None => map.push((entry.key.clone(), value)),
+                    }
+                }
+                Ok(Literal::from(map))
             }
-            AbstractSyntaxTree::ElseStatement(if_or_block_statement) => {
-                self.evaluate_statement(if_or_block_statement, block)
+            AbstractSyntaxTree::MemberExpression(map, key, _) => {
+                self.evaluate_statement(map, environment)?.member(key)
             }
-            AbstractSyntaxTree::LoopStatement(condition, block_to_execute) => {
-                self.evaluate_loop_statement(condition, block_to_execute, block)
+            // a range anywhere but a `loop for` is the list of its numbers
+            AbstractSyntaxTree::RangeExpression(start, end, inclusive, step, _) => {
+                let range = self.evaluate_range(start, end, *inclusive, step, environment)?;
+                if range.len() > MAX_LIST_LENGTH {
+                    return Err(CompilerError::RangeTooLong(MAX_LIST_LENGTH));
+                }
+                Ok(Literal::from(range.map(Literal::from).collect::<Vec<_>>()))
             }
-            AbstractSyntaxTree::CallStatement(name, arguements) => {
-                self.evalute_call_statement(name.to_string(), arguements, block)
+            AbstractSyntaxTree::IndexExpression(list, index, _) => {
+                let list = self.evaluate_statement(list, environment)?;
+                let index = self.evaluate_statement(index, environment)?;
+                list.index(&index)
             }
-            AbstractSyntaxTree::ReturnStatement(statement) => {
-                if !block.read().unwrap().is_function {
-                    return Err(CompilerError::ReturnOutsideFunction);
-                }
-                Ok(Literal::new(
-                    DataType::Return(Box::new(self.evaluate_statement(statement, block)?)),
-                    false,
-                ))
-            }
-            AbstractSyntaxTree::BreakStatement(statement) => {
-                if !block.read().unwrap().is_loop {
-                    return Err(CompilerError::BreakOutsideLoop);
-                }
-                Ok(Literal::new(
-                    DataType::Break(Box::new(self.evaluate_statement(statement, block)?)),
-                    false,
-                ))
-            }
-            AbstractSyntaxTree::SkipStatement(statement) => {
-                if !block.read().unwrap().is_loop {
-                    return Err(CompilerError::SkipOutsideLoop);
+            AbstractSyntaxTree::AssignmentExpression(target, operator, expression, _) => {
+                match target.as_ref() {
+                    AbstractSyntaxTree::Identifier(name, _) => self.evaluate_assignment_expression(
+                        name,
+                        operator,
+                        expression,
+                        false,
+                        environment,
+                    ),
+                    target => {
+                        self.evaluate_element_assignment(target, operator, expression, environment)
+                    }
                 }
-                Ok(Literal::new(
-                    DataType::Skip(Box::new(self.evaluate_statement(statement, block)?)),
-                    false,
-                ))
             }
+            AbstractSyntaxTree::MutableAssignmentExpression(name, operator, expression, _) => {
+                self.evaluate_assignment_expression(name, operator, expression, true, environment)
+            }
+            AbstractSyntaxTree::ParenthesizedExpression(expression, _) => {
+                self.evaluate_statement(expression, environment)
+            }
+            AbstractSyntaxTree::BlockStatement(block, _) => {
+                let environment = Arc::new(Environment::from(Arc::clone(environment)));
+                let result = self.evaluate_block(block, &environment);
+                self.release(environment);
+                result
+            }
+            AbstractSyntaxTree::IfStatement(
+                condition,
+                if_block_or_statement,
+                else_statement,
+                _,
+            ) => self.evaluate_if_statement(
+                condition,
+                if_block_or_statement,
+                else_statement,
+                environment,
+            ),
+            AbstractSyntaxTree::ElseStatement(if_or_block_statement, _) => {
+                self.evaluate_statement(if_or_block_statement, environment)
+            }
+            AbstractSyntaxTree::LoopStatement(condition, block_to_execute, _) => {
+                self.evaluate_loop_statement(condition, block_to_execute, environment)
+            }
+            AbstractSyntaxTree::ForStatement(names, iterable, block_to_execute, _) => {
+                self.evaluate_for_statement(names, iterable, block_to_execute, environment)
+            }
+            AbstractSyntaxTree::CallStatement(callee, arguements, _) => {
+                self.evalute_call_statement(callee, arguements, environment)
+            }
+            AbstractSyntaxTree::ReturnStatement(statement, _) => Ok(Literal::new(
+                DataType::Return(Box::new(self.evaluate_statement(statement, environment)?)),
+                false,
+            )),
+            AbstractSyntaxTree::BreakStatement(statement, _) => Ok(Literal::new(
+                DataType::Break(Box::new(self.evaluate_statement(statement, environment)?)),
+                false,
+            )),
+            AbstractSyntaxTree::SkipStatement(statement, _) => Ok(Literal::new(
+                DataType::Skip(Box::new(self.evaluate_statement(statement, environment)?)),
+                false,
+            )),
         }
     }
 
+    // anything that evaluates to a function can be called, built in functions included
     fn evalute_call_statement(
         &self,
-        name: String,
+        callee: &AbstractSyntaxTree,
         arguements: &SeperatedStatements<Box<AbstractSyntaxTree>>,
-        block: Arc<RwLock<Block>>,
+        environment: &Arc<Environment>,
     ) -> Result<Literal, CompilerError> {
         if Symbol::OpenParanthesis != arguements.enclosed_with {
             return Err(CompilerError::InvalidEncloser(arguements.enclosed_with));
@@ -123,73 +214,210 @@ impl Evaluator {
             return Err(CompilerError::InvalidSeperator(arguements.seperated_with));
         }
 
+        if let AbstractSyntaxTree::Identifier(name, _) = callee {
+            if !environment.contains_symbol(name)
+                && self.global.get_built_in_properties(name).is_none()
+            {
+                return Err(CompilerError::UndefinedFunction(name.clone()));
+            }
+        }
+        match self.evaluate_statement(callee, environment)?.value {
+            DataType::Function(function) => {
+                let arguements = self.evaluate_arguements(arguements, environment)?;
+                self.call_function(&callee.to_string(), &function, arguements, environment)
+            }
+            DataType::BuiltIn(function) => {
+                function.call(self.evaluate_arguements(arguements, environment)?)
+            }
+            _ => Err(CompilerError::NotAFunction(callee.to_string())),
+        }
+    }
+
+    fn evaluate_arguements(
+        &self,
+        arguements: &SeperatedStatements<Box<AbstractSyntaxTree>>,
+        environment: &Arc<Environment>,
+    ) -> Result<Vec<Literal>, CompilerError> {
         let mut evaluated_arguements: Vec<Literal> = vec![];
         for arguement in arguements.iter() {
-            let evaluated_arguement = self.evaluate_statement(arguement, Arc::clone(&block))?;
+            let evaluated_arguement = self.evaluate_statement(arguement, environment)?;
             evaluated_arguements.push(evaluated_arguement);
         }
-        if let Some(function) = Arc::clone(&block).read().unwrap().get_symbol(&name) {
-            if let DataType::Function(function) = function.value {
-                if evaluated_arguements.len() != function.parameters.len() {
-                    return Err(CompilerError::ArgumentLengthMismatch(
-                        name.clone(),
-                        function.parameters.len(),
-                        evaluated_arguements.len(),
-                    ));
-                }
-                for (index, parameter) in function.parameters.iter().enumerate() {
-                    if let AbstractSyntaxTree::Identifier(name) = parameter {
-                        function
-                            .block
-                            .read()
-                            .unwrap()
-                            .add_symbol(name.clone(), evaluated_arguements[index].clone());
-                    }
-                }
+        Ok(evaluated_arguements)
+    }
 
-                return self.evaluate_block(Arc::clone(&function.block));
-            } else {
-                return Err(CompilerError::NotAFunction(name));
-            }
+    fn call_function(
+        &self,
+        name: &str,
+        function: &Function,
+        arguements: Vec<Literal>,
+        environment: &Arc<Environment>,
+    ) -> Result<Literal, CompilerError> {
+        if arguements.len() != function.parameters.len() {
+            return Err(CompilerError::ArgumentLengthMismatch(
+                name.to_string(),
+                function.parameters.len(),
+                arguements.len(),
+            ));
         }
-        match self.global.get_built_in_function(&name) {
-            Some(built_in_function) => built_in_function(evaluated_arguements),
-            None => Err(CompilerError::UndefinedFunction(name)),
+        if self.depth.get() == MAX_CALL_DEPTH {
+            return Err(CompilerError::CallDepthExceeded(MAX_CALL_DEPTH));
         }
+        // every call gets its own frame, so a recursive call keeps the parameters of its caller.
+        // the frame sits inside the environment the function was created in, not the caller's
+        let parent = function.environment.as_ref().unwrap_or(environment);
+        let frame = Arc::new(Environment::from(Arc::clone(parent)));
+        for (parameter, arguement) in function.parameters.iter().zip(arguements) {
+            if let AbstractSyntaxTree::Identifier(name, _) = parameter {
+                frame.define_symbol(name.clone(), arguement.into_immutable());
+            }
+        }
+
+        self.depth.set(self.depth.get() + 1);
+        let result = self.evaluate_block(&function.block, &frame);
+        self.depth.set(self.depth.get() - 1);
+        self.release(frame);
+        let result = result?;
+        // a return from any depth of the function ends here
+        Ok(match result.value {
+            DataType::Return(value) => *value,
+            _ => result,
+        })
+    }
+
+    fn release(&self, environment: Arc<Environment>) {
+        self.global.releases.lock().unwrap().release(environment);
     }
 
     fn evaluate_loop_statement(
         &self,
         condition_statement: &AbstractSyntaxTree,
         block_or_statement_to_execute: &AbstractSyntaxTree,
-        block: Arc<RwLock<Block>>,
+        environment: &Arc<Environment>,
     ) -> Result<Literal, CompilerError> {
         let mut result = Literal::from(false);
         let mut skip_count = 0;
         while self
-            .evaluate_statement(condition_statement, Arc::clone(&block))?
+            .evaluate_statement(condition_statement, environment)?
             .is_truthy()?
         {
             if skip_count > 0 {
                 skip_count -= 1;
                 continue;
             }
-            result = self.evaluate_statement(block_or_statement_to_execute, Arc::clone(&block))?;
-            if let DataType::Break(value_to_return) = result.value {
-                result = *value_to_return;
+            // the value of the last round would make its lists look shared to this one
+            drop(result);
+            let frame = Arc::new(Environment::from(Arc::clone(environment)));
+            let round = self.evaluate_statement(block_or_statement_to_execute, &frame);
+            self.release(frame);
+            result = round?;
+            if Self::ends_loop(&mut result, &mut skip_count)? {
                 break;
             }
-            if let DataType::Skip(count) = result.value.clone() {
-                if let DataType::Integer(count) = count.value {
-                    skip_count = count;
-                } else {
+        }
+        Ok(result)
+    }
+
+    fn evaluate_for_statement(
+        &self,
+        names: &[String],
+        iterable: &AbstractSyntaxTree,
+        block_or_statement_to_execute: &AbstractSyntaxTree,
+        environment: &Arc<Environment>,
+    ) -> Result<Literal, CompilerError> {
+        // a single name is given the keys of a map and the items of anything else
+        let mut takes_key = false;
+        let mut iterable = iterable;
+        while let AbstractSyntaxTree::ParenthesizedExpression(inner, _) = iterable {
+            iterable = inner;
+        }
+        let rounds: Box<dyn Iterator<Item = (Literal, Literal)>> = match iterable {
+            AbstractSyntaxTree::RangeExpression(start, end, inclusive, step, _) => {
+                let range = self.evaluate_range(start, end, *inclusive, step, environment)?;
+                Box::new(range.enumerate().map(|(position, number)| {
+                    (Literal::from(position as i128), Literal::from(number))
+                }))
+            }
+            iterable => {
+                let iterable = self.evaluate_statement(iterable, environment)?;
+                takes_key = matches!(iterable.value, DataType::Map(_));
+                Box::new(iterable.entries()?.into_iter())
+            }
+        };
+
+        let mut result = Literal::from(false);
+        let mut skip_count = 0;
+        for (key, item) in rounds {
+            if skip_count > 0 {
+                skip_count -= 1;
+                continue;
+            }
+            drop(result);
+            // every round has its own bindings, a function made in one keeps its values
+            let frame = Arc::new(Environment::from(Arc::clone(environment)));
+            match names {
+                [name] => {
+                    let value = if takes_key { key } else { item };
+                    frame.define_symbol(name.clone(), value.into_immutable());
+                }
+                [key_name, item_name] => {
+                    frame.define_symbol(key_name.clone(), key.into_immutable());
+                    frame.define_symbol(item_name.clone(), item.into_immutable());
+                }
+                _ => {}
+            }
+            let round = self.evaluate_statement(block_or_statement_to_execute, &frame);
+            self.release(frame);
+            result = round?;
+            if Self::ends_loop(&mut result, &mut skip_count)? {
+                break;
+            }
+        }
+        Ok(result)
+    }
+
+    // a `return` ends the loop and is passed on, a `break` ends it with its value
+    // and a `skip n` leaves out the next n rounds
+    fn ends_loop(result: &mut Literal, skip_count: &mut i128) -> Result<bool, CompilerError> {
+        match &result.value {
+            DataType::Return(_) => Ok(true),
+            DataType::Break(value_to_return) => {
+                *result = value_to_return.as_ref().clone();
+                Ok(true)
+            }
+            DataType::Skip(count) => {
+                let DataType::Integer(count) = count.value else {
                     return Err(CompilerError::SkipCountTypeMisMatch(
                         count.value.to_string(),
                     ));
-                }
+                };
+                *skip_count = count;
+                Ok(false)
             }
+            _ => Ok(false),
         }
-        Ok(result)
+    }
+
+    fn evaluate_range(
+        &self,
+        start: &AbstractSyntaxTree,
+        end: &AbstractSyntaxTree,
+        inclusive: bool,
+        step: &Option<Box<AbstractSyntaxTree>>,
+        environment: &Arc<Environment>,
+    ) -> Result<Range, CompilerError> {
+        let bound = |statement: &AbstractSyntaxTree| match self
+            .evaluate_statement(statement, environment)?
+            .value
+        {
+            DataType::Integer(bound) => Ok(bound),
+            other => Err(CompilerError::InvalidRangeBound(other.to_string())),
+        };
+        let step = match step {
+            Some(step) => bound(step)?,
+            None => 1,
+        };
+        Range::new(bound(start)?, bound(end)?, inclusive, step)
     }
 
     fn evaluate_if_statement(
@@ -197,98 +425,181 @@ impl Evaluator {
         condition: &AbstractSyntaxTree,
         if_block_or_statement: &AbstractSyntaxTree,
         else_statement: &Option<Box<AbstractSyntaxTree>>,
-        scope_block: Arc<RwLock<Block>>,
+        environment: &Arc<Environment>,
     ) -> Result<Literal, CompilerError> {
-        let condition = self.evaluate_statement(condition, Arc::clone(&scope_block))?;
+        let condition = self.evaluate_statement(condition, environment)?;
         if condition.is_truthy()? {
-            self.evaluate_statement(if_block_or_statement, Arc::clone(&scope_block))
+            self.evaluate_statement(if_block_or_statement, environment)
         } else if let Some(else_block) = else_statement {
-            self.evaluate_statement(else_block, scope_block)
+            self.evaluate_statement(else_block, environment)
         } else {
             Ok(Literal::from(false))
         }
     }
 
-    fn evaluate_block(&self, block: Arc<RwLock<Block>>) -> Result<Literal, CompilerError> {
+    // the statements run in `environment`, which belongs to this run of the block alone
+    fn evaluate_block(
+        &self,
+        block: &RwLock<Block>,
+        environment: &Arc<Environment>,
+    ) -> Result<Literal, CompilerError> {
         let mut result = Literal::from(false);
         for statement in block.read().unwrap().statements.iter() {
-            result = self.evaluate_statement(statement, Arc::clone(&block))?;
-            if let DataType::Return(statement) = result.value {
-                result = *statement;
-                break;
-            }
-            if let DataType::Break(_) = result.value {
-                break;
-            }
-            if let DataType::Skip(_) = result.value {
+            result = self.evaluate_statement(statement, environment)?;
+            // left for the enclosing function or loop
+            if let DataType::Return(_) | DataType::Break(_) | DataType::Skip(_) = result.value {
                 break;
             }
         }
-        block.read().unwrap().clear_symbols();
         Ok(result)
     }
 
+    // `let` makes a new variable mutable, or changes one that already is
     fn evaluate_assignment_expression(
         &self,
         name: &str,
         operator: &Operator,
         expression: &AbstractSyntaxTree,
-        block: Arc<RwLock<Block>>,
+        mutable: bool,
+        environment: &Arc<Environment>,
     ) -> Result<Literal, CompilerError> {
-        let right_hand = self.evaluate_statement(expression, Arc::clone(&block))?;
-        let block = block.read().unwrap();
-        match operator {
-            Operator::Assignment(assigmnent) => match assigmnent {
-                Assingment::Simple => {
-                    if let Some(old_variable) = block.get_symbol(name) {
-                        if old_variable.is_mutable() {
-                            block.add_symbol(name.to_string(), right_hand.clone().to_mutable());
-                        } else {
-                            return Err(CompilerError::ImmutableVariable(name.to_string()));
-                        }
-                    } else {
-                        block.add_symbol(name.to_string(), right_hand.clone());
-                    }
-                    Ok(right_hand)
+        let right_hand = self.evaluate_statement(expression, environment)?;
+        let Operator::Assignment(assignment) = operator else {
+            return Err(CompilerError::InvalidOperatorForBinaryOperation(*operator));
+        };
+        match (environment.get_symbol(name), assignment) {
+            (Some(old_variable), _) if !old_variable.is_mutable() => {
+                if mutable {
+                    Err(CompilerError::CannotConvertFromImmutableToMutable)
+                } else {
+                    Err(CompilerError::ImmutableVariable(name.to_string()))
                 }
-                assignment_operator => {
-                    if let Some(old_variable) = block.get_symbol(name) {
-                        if old_variable.is_mutable() {
-                            let result = assignment_operator.evaluate(old_variable, right_hand)?;
-                            block.add_symbol(name.to_string(), result.clone().to_mutable());
-                            Ok(result)
-                        } else {
-                            Err(CompilerError::ImmutableVariable(name.to_string()))
-                        }
-                    } else {
-                        Err(CompilerError::UndefinedVariable(name.to_string()))
-                    }
+            }
+            (Some(_), Assingment::Simple) => {
+                environment.add_symbol(name.to_string(), right_hand.to_mutable());
+                Ok(right_hand)
+            }
+            (Some(old_variable), Assingment::Addition)
+                if matches!(
+                    (&old_variable.value, &right_hand.value),
+                    (DataType::List(_), DataType::List(_))
+                ) =>
+            {
+                drop(old_variable);
+                let result = environment.update_symbol(name, |variable| {
+                    variable.append(&right_hand);
+                    variable.clone()
+                });
+                Ok(result.expect("a defined variable").into_immutable())
+            }
+            (Some(old_variable), assignment_operator) => {
+                let result = assignment_operator.evaluate(old_variable, right_hand)?;
+                environment.add_symbol(name.to_string(), result.to_mutable());
+                Ok(result)
+            }
+            (None, Assingment::Simple) => {
+                let variable = if mutable {
+                    right_hand.to_mutable()
+                } else {
+                    right_hand.clone().into_immutable()
+                };
+                environment.define_symbol(name.to_string(), variable);
+                Ok(right_hand)
+            }
+            (None, _) => Err(CompilerError::UndefinedVariable(name.to_string())),
+        }
+    }
+
+    // `xs[0] = v` and `user.name = v` change the list or map of a mutable variable in place.
+    // other values that share it keep what they had, so a list can not end up inside itself
+    fn evaluate_element_assignment(
+        &self,
+        target: &AbstractSyntaxTree,
+        operator: &Operator,
+        expression: &AbstractSyntaxTree,
+        environment: &Arc<Environment>,
+    ) -> Result<Literal, CompilerError> {
+        let Operator::Assignment(assignment) = operator else {
+            return Err(CompilerError::InvalidOperatorForBinaryOperation(*operator));
+        };
+        let name = target
+            .assigned_variable()
+            .ok_or(CompilerError::InvalidAssignment)?;
+        match environment.get_symbol(name) {
+            Some(variable) if !variable.is_mutable() => {
+                return Err(CompilerError::ImmutableVariable(name.to_string()))
+            }
+            Some(_) => {}
+            None => return Err(CompilerError::UndefinedVariable(name.to_string())),
+        }
+
+        let right_hand = self.evaluate_statement(expression, environment)?;
+        let mut path = Vec::new();
+        self.element_path(target, &mut path, environment)?;
+        let value = match assignment {
+            Assingment::Simple => right_hand,
+            assignment => {
+                // nothing read here is kept, or the lists on the way would look shared when set
+                let mut old_value = environment
+                    .get_symbol(name)
+                    .ok_or_else(|| CompilerError::UndefinedVariable(name.to_string()))?;
+                for element in path.iter() {
+                    old_value = old_value.element(element)?;
                 }
-            },
-            operator => Err(CompilerError::InvalidOperatorForBinaryOperation(*operator)),
+                assignment.evaluate(old_value, right_hand)?
+            }
+        };
+        environment
+            .update_symbol(name, |variable| {
+                variable.set_element(&path, value.clone().into_immutable())
+            })
+            .ok_or_else(|| CompilerError::UndefinedVariable(name.to_string()))??;
+        Ok(value)
+    }
+
+    // the indices and keys that lead from the assigned variable to the element that changes
+    fn element_path(
+        &self,
+        target: &AbstractSyntaxTree,
+        path: &mut Vec<Element>,
+        environment: &Arc<Environment>,
+    ) -> Result<(), CompilerError> {
+        match target {
+            AbstractSyntaxTree::Identifier(..) => {}
+            AbstractSyntaxTree::IndexExpression(container, index, _) => {
+                self.element_path(container, path, environment)?;
+                let index = self.evaluate_statement(index, environment)?;
+                path.push(Element::Index(index));
+            }
+            AbstractSyntaxTree::MemberExpression(container, key, _) => {
+                self.element_path(container, path, environment)?;
+                path.push(Element::Member(key.clone()));
+            }
+            _ => return Err(CompilerError::InvalidAssignment),
         }
+        Ok(())
     }
 
     fn evaluate_unary_expression(
         &self,
         operator: &Operator,
         expression: &AbstractSyntaxTree,
-        block: Arc<RwLock<Block>>,
+        environment: &Arc<Environment>,
     ) -> Result<Literal, CompilerError> {
         match operator {
-            Arithmetic(operator) => match operator {
-                Addition => {
-                    Ok(Addition.evaluate_unary(self.evaluate_statement(expression, block)?)?)
+            Arithmetic(operator) => {
+                match operator {
+                    Addition => Ok(Addition
+                        .evaluate_unary(self.evaluate_statement(expression, environment)?)?),
+                    Subtraction => Ok(Subtraction
+                        .evaluate_unary(self.evaluate_statement(expression, environment)?)?),
+                    operator => Err(CompilerError::InvalidOperatorForUnaryOperation(
+                        Operator::Arithmetic(*operator),
+                    )),
                 }
-                Subtraction => {
-                    Ok(Subtraction.evaluate_unary(self.evaluate_statement(expression, block)?)?)
-                }
-                operator => Err(CompilerError::InvalidOperatorForUnaryOperation(
-                    Operator::Arithmetic(*operator),
-                )),
-            },
+            }
             Logical(operator) => match operator {
-                Not => Ok(Not.evaluate_unary(self.evaluate_statement(expression, block)?)?),
+                Not => Ok(Not.evaluate_unary(self.evaluate_statement(expression, environment)?)?),
                 operator => Err(CompilerError::InvalidOperatorForUnaryOperation(
                     Operator::Logical(*operator),
                 )),
@@ -300,12 +611,12 @@ impl Evaluator {
     fn evaluate_binary_expression(
         &self,
         left: &AbstractSyntaxTree,
-        block: Arc<RwLock<Block>>,
+        environment: &Arc<Environment>,
         right: &AbstractSyntaxTree,
         operator: &Operator,
     ) -> Result<Literal, CompilerError> {
-        let left = self.evaluate_statement(left, Arc::clone(&block))?;
-        let right = self.evaluate_statement(right, block)?;
+        let left = self.evaluate_statement(left, environment)?;
+        let right = self.evaluate_statement(right, environment)?;
         let result = match operator {
             Arithmetic(_) | Relational(_) | Logical(_) => operator.evaluate(left, right)?,
             operator => return Err(CompilerError::InvalidOperatorForBinaryOperation(*operator)),
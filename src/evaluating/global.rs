@@ -1,9 +1,12 @@
 use std::io::Write;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex};
 
+use crate::common::functions::BuiltInFunction;
 use crate::common::literal::Literal;
 use crate::common::{datatypes::DataType, errors::CompilerError};
-use crate::parsing::block::Block;
+use crate::resolving::scope::Scope;
+
+use super::environment::{Environment, PendingReleases};
 use std::io::{stdin, stdout};
 
 fn to_number(variables: Vec<Literal>) -> Result<Literal, CompilerError> {
@@ -29,6 +32,45 @@ fn to_number(variables: Vec<Literal>) -> Result<Literal, CompilerError> {
     }
 }
 
+fn length(variables: Vec<Literal>) -> Result<Literal, CompilerError> {
+    if variables.len() != 1 {
+        return Err(CompilerError::ArgumentLengthMismatch(
+            "len".to_string(),
+            1,
+            variables.len(),
+        ));
+    }
+    let variable = variables.first().unwrap();
+    match &variable.value {
+        DataType::List(list) => Ok(Literal::from(list.read().unwrap().len() as i128)),
+        DataType::Map(map) => Ok(Literal::from(map.read().unwrap().len() as i128)),
+        DataType::String(string) => Ok(Literal::from(string.chars().count() as i128)),
+        _ => Err(CompilerError::InvalidType(variable.value.to_string())),
+    }
+}
+
+fn has(variables: Vec<Literal>) -> Result<Literal, CompilerError> {
+    if variables.len() != 2 {
+        return Err(CompilerError::ArgumentLengthMismatch(
+            "has".to_string(),
+            2,
+            variables.len(),
+        ));
+    }
+    match (&variables[0].value, &variables[1].value) {
+        (DataType::Map(map), DataType::String(key)) => {
+            let found = map
+                .read()
+                .unwrap()
+                .iter()
+                .any(|(name, _)| name == key.as_str());
+            Ok(Literal::from(found))
+        }
+        (DataType::Map(_), key) => Err(CompilerError::InvalidKey(key.to_string())),
+        (value, _) => Err(CompilerError::InvalidType(value.to_string())),
+    }
+}
+
 fn print(variables: Vec<Literal>) -> Result<Literal, CompilerError> {
     for variable in variables.iter() {
         print!("{}", variable);
@@ -51,45 +93,64 @@ fn input(variables: Vec<Literal>) -> Result<Literal, CompilerError> {
     Ok(Literal::from(buffer))
 }
 
-type BuiltInFunction = fn(Vec<Literal>) -> Result<Literal, CompilerError>;
 enum BuiltInAttributes {
-    BuiltInFunctions(String, BuiltInFunction),
+    BuiltInFunctions(BuiltInFunction),
     BuiltInProperties(String, Literal),
 }
 
 pub(crate) struct Global {
     properties: Vec<BuiltInAttributes>,
-    pub(crate) block: Arc<RwLock<Block>>,
+    pub(crate) environment: Arc<Environment>,
+    // the variables the resolver knows to be declared by the programs run so far
+    pub(crate) scope: Mutex<Scope>,
+    pub(crate) releases: Mutex<PendingReleases>,
 }
 
 impl Global {
     pub(crate) fn new() -> Self {
         Self {
-            block: Arc::new(RwLock::new(Block::new())),
+            environment: Arc::new(Environment::new()),
+            scope: Mutex::new(Scope::default()),
+            releases: Mutex::new(PendingReleases::default()),
             properties: vec![
-                BuiltInAttributes::BuiltInFunctions("print".to_string(), print),
-                BuiltInAttributes::BuiltInFunctions("input".to_string(), input),
-                BuiltInAttributes::BuiltInFunctions("number".to_string(), to_number),
+                BuiltInAttributes::BuiltInFunctions(BuiltInFunction::new("print", print)),
+                BuiltInAttributes::BuiltInFunctions(BuiltInFunction::new("input", input)),
+                BuiltInAttributes::BuiltInFunctions(BuiltInFunction::new("number", to_number)),
+                BuiltInAttributes::BuiltInFunctions(BuiltInFunction::new("len", length)),
+                BuiltInAttributes::BuiltInFunctions(BuiltInFunction::new("has", has)),
                 BuiltInAttributes::BuiltInProperties("lucky".to_string(), Literal::from(7)),
             ],
         }
     }
-    pub(crate) fn get_built_in_function(&self, name: &str) -> Option<&BuiltInFunction> {
-        for property in self.properties.iter() {
-            if let BuiltInAttributes::BuiltInFunctions(function_name, function) = property {
-                if name == function_name {
-                    return Some(function);
-                }
-            }
+    // exposes the command line arguments as `argc` and `arg0`..`argN`,
+    // where `arg0` is the script itself
+    pub(crate) fn with_arguments(arguments: Vec<String>) -> Self {
+        let mut global = Self::new();
+        global.properties.push(BuiltInAttributes::BuiltInProperties(
+            "argc".to_string(),
+            Literal::from(arguments.len().saturating_sub(1) as i128),
+        ));
+        for (index, argument) in arguments.into_iter().enumerate() {
+            global.properties.push(BuiltInAttributes::BuiltInProperties(
+                format!("arg{index}"),
+                Literal::from(argument),
+            ));
         }
-        None
+        global
     }
+    // built in functions are values too, they can be passed around like any other function
     pub(crate) fn get_built_in_properties(&self, name: &str) -> Option<Literal> {
         for property in self.properties.iter() {
-            if let BuiltInAttributes::BuiltInProperties(property_name, property_value) = property {
-                if name == property_name {
+            match property {
+                BuiltInAttributes::BuiltInFunctions(function) if name == function.name => {
+                    return Some(Literal::from(DataType::BuiltIn(*function)));
+                }
+                BuiltInAttributes::BuiltInProperties(property_name, property_value)
+                    if name == property_name =>
+                {
                     return Some(property_value.clone());
                 }
+                _ => {}
             }
         }
         None
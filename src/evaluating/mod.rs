@@ -1,2 +1,4 @@
+pub(crate) mod environment;
 pub(crate) mod evaluator;
 pub(crate) mod global;
+pub(crate) mod range;
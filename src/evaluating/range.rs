@@ -0,0 +1,73 @@
+use crate::common::errors::CompilerError;
+
+// a range outside a loop becomes a list, a longer one can only be looped over
+pub(crate) const MAX_LIST_LENGTH: u128 = 10_000_000;
+
+// the numbers of `start..end step n`, counted as a loop goes instead of kept in a list
+pub(crate) struct Range {
+    next: Option<i128>,
+    end: i128,
+    inclusive: bool,
+    step: i128,
+}
+
+impl Range {
+    pub(crate) fn new(
+        start: i128,
+        end: i128,
+        inclusive: bool,
+        step: i128,
+    ) -> Result<Self, CompilerError> {
+        if step == 0 {
+            return Err(CompilerError::RangeStepZero);
+        }
+        Ok(Self {
+            next: Some(start),
+            end,
+            inclusive,
+            step,
+        })
+    }
+
+    // how many numbers are left, without counting them one by one
+    pub(crate) fn len(&self) -> u128 {
+        let Some(start) = self.next else {
+            return 0;
+        };
+        let reaches_end = if self.step > 0 {
+            start < self.end
+        } else {
+            start > self.end
+        };
+        let distance = start.abs_diff(self.end);
+        let step = self.step.unsigned_abs();
+        if self.inclusive && (reaches_end || start == self.end) {
+            (distance / step).saturating_add(1)
+        } else if reaches_end {
+            (distance - 1) / step + 1
+        } else {
+            0
+        }
+    }
+}
+
+// a negative step counts down from the start to the end
+impl Iterator for Range {
+    type Item = i128;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        let in_range = match (self.step > 0, self.inclusive) {
+            (true, true) => current <= self.end,
+            (true, false) => current < self.end,
+            (false, true) => current >= self.end,
+            (false, false) => current > self.end,
+        };
+        if !in_range {
+            self.next = None;
+            return None;
+        }
+        self.next = current.checked_add(self.step);
+        Some(current)
+    }
+}
@@ -0,0 +1,159 @@
+use std::io::{Cursor, Read};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use crate::common::errors::CompilerError;
+use crate::common::literal::Literal;
+use crate::evaluating::{evaluator::Evaluator, global::Global};
+use crate::lexing::{FileReader, Lexer};
+use crate::parsing::Parser;
+use crate::resolving::Resolver;
+
+// every call of a prolang function goes through a few nested rust calls,
+// the default stack of a thread only fits a few dozen of them in a debug build.
+// only the part that is used is ever given memory
+const EVALUATOR_STACK_SIZE: usize = 256 * 1024 * 1024;
+
+/// Runs prolang programs.
+///
+/// Every interpreter owns its own globals, so variables defined by one call to
+/// [`Interpreter::evaluate`] are visible to the next ones, and separate interpreters
+/// never see each other's variables.
+///
+/// Every source given to an interpreter gets the next file id, starting at 0,
+/// which is the `file_id` of the spans in its errors.
+pub struct Interpreter {
+    global: Arc<Global>,
+    sources: AtomicU32,
+}
+
+impl Interpreter {
+    pub fn new() -> Self {
+        Self {
+            global: Arc::new(Global::new()),
+            sources: AtomicU32::new(0),
+        }
+    }
+
+    /// The program sees the arguments as `arg0`..`argN` and their count (without `arg0`) as `argc`.
+    pub fn with_arguments(arguments: Vec<String>) -> Self {
+        Self {
+            global: Arc::new(Global::with_arguments(arguments)),
+            sources: AtomicU32::new(0),
+        }
+    }
+
+    /// Evaluates the code and returns the value of its last statement.
+    pub fn evaluate(&self, source: &str) -> Result<Literal, CompilerError> {
+        self.evaluate_reader(Cursor::new(source.to_string().into_bytes()))
+    }
+
+    pub fn evaluate_reader(
+        &self,
+        reader: impl Read + Send + 'static,
+    ) -> Result<Literal, CompilerError> {
+        self.execute(reader, true)
+    }
+
+    /// Lexes, parses and resolves the code without evaluating it.
+    pub fn check(&self, source: &str) -> Result<(), CompilerError> {
+        self.check_reader(Cursor::new(source.to_string().into_bytes()))
+    }
+
+    pub fn check_reader(&self, reader: impl Read + Send + 'static) -> Result<(), CompilerError> {
+        self.execute(reader, false).map(|_| ())
+    }
+
+    /// Forgets every variable defined so far.
+    pub fn reset(&mut self) {
+        self.global = Arc::new(Global::new());
+    }
+
+    pub(crate) fn global(&self) -> &Arc<Global> {
+        &self.global
+    }
+
+    // wires up FileReader -> Lexer -> Parser -> Resolver, each stage on its own thread.
+    // the evaluator only starts when all of them finished without errors, and is left out
+    // when `evaluate` is false
+    fn execute(
+        &self,
+        reader: impl Read + Send + 'static,
+        evaluate: bool,
+    ) -> Result<Literal, CompilerError> {
+        // channels
+        let (file_chunk_transmitter, file_chunk_receiver) = mpsc::channel();
+        let (token_transmitter, token_receiver) = mpsc::channel();
+        let (statement_transmitter, statement_receiver) = mpsc::channel();
+
+        let file_reader = FileReader::new(reader, file_chunk_transmitter);
+        let file_id = self.sources.fetch_add(1, Ordering::Relaxed);
+        let lexer = Lexer::new(file_chunk_receiver, token_transmitter, file_id);
+        let parser = Parser::new(token_receiver, statement_transmitter);
+        let resolver = Resolver::new(statement_receiver, Arc::clone(&self.global));
+        let global = Arc::clone(&self.global);
+
+        thread::scope(move |scope| {
+            let resolving = scope.spawn(move || resolver.resolve());
+            let parsing = scope.spawn(move || parser.parse());
+            let lexing = scope.spawn(move || lexer.lex());
+            let read = file_reader.read();
+
+            let lexed = lexing
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+            let parsed = parsing
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+            let resolved = resolving
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+            // the other stages only saw the part of the source before the error
+            read?;
+
+            let statements = match (lexed, parsed, resolved) {
+                (Ok(()), Ok(()), Ok((statements, scope))) => {
+                    if !evaluate {
+                        return Ok(Literal::from(true));
+                    }
+                    *self.global.scope.lock().unwrap() = scope;
+                    statements
+                }
+                // the resolver only saw part of a program with lexical or syntax errors,
+                // it may not know the names declared in the rest, so its errors are left out then
+                (Ok(()), Ok(()), Err(err)) => return Err(err),
+                (lexed, parsed, _) => {
+                    // reported together, in the order they appear in the source
+                    let mut errors: Vec<CompilerError> = [lexed.err(), parsed.err()]
+                        .into_iter()
+                        .flatten()
+                        .flat_map(CompilerError::into_errors)
+                        .collect();
+                    errors.sort_by_key(|err| err.span().map(|span| span.start_byte));
+                    return CompilerError::from_errors(errors).map(|_| unreachable!());
+                }
+            };
+
+            let (resolved_transmitter, resolved_receiver) = mpsc::channel();
+            for statement in statements {
+                resolved_transmitter.send(statement).unwrap();
+            }
+            drop(resolved_transmitter);
+            thread::Builder::new()
+                .stack_size(EVALUATOR_STACK_SIZE)
+                .spawn_scoped(scope, move || {
+                    Evaluator::new(resolved_receiver, global).evaluate()
+                })
+                .expect("failed to start the evaluator")
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+        })
+    }
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,32 +1,44 @@
-use std::{collections::VecDeque, io::Read, sync::mpsc::Sender};
+use std::{
+    collections::VecDeque,
+    io::{ErrorKind, Read},
+    sync::mpsc::Sender,
+};
+
+use crate::common::errors::CompilerError;
 
 pub(crate) struct FileReader {
-    file: String,
+    reader: Box<dyn Read + Send>,
     file_chunk_transmitter: Sender<VecDeque<u8>>,
 }
 
 impl FileReader {
     pub(crate) fn new(
-        file_name: impl Into<String>,
+        reader: impl Read + Send + 'static,
         file_chunk_transmitter: Sender<VecDeque<u8>>,
     ) -> Self {
         Self {
-            file: file_name.into(),
+            reader: Box::new(reader),
             file_chunk_transmitter,
         }
     }
 
-    pub(crate) fn read(&self) {
+    // a source that cannot be read to the end is an error, the lexer only sees the part before it
+    pub(crate) fn read(mut self) -> Result<(), CompilerError> {
         const BUFFER_SIZE: usize = 1024;
-        let file = std::fs::File::open(&self.file).expect("Unable to open file");
-        let mut reader = std::io::BufReader::new(file);
         let mut buf = [0; BUFFER_SIZE];
-        while let Ok(count) = reader.read(&mut buf) {
-            if count == 0 {
-                break;
+        loop {
+            let count = match self.reader.read(&mut buf) {
+                Ok(0) => return Ok(()),
+                Ok(count) => count,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(CompilerError::ReadError(err)),
+            };
+            // a chunk may end in the middle of a character, the lexer puts it back together
+            let chunk = buf[..count].iter().copied().collect();
+            if self.file_chunk_transmitter.send(chunk).is_err() {
+                // lexer stopped listening, nothing left to read for
+                return Ok(());
             }
-            self.file_chunk_transmitter.send(buf.into()).unwrap();
-            buf = [0; BUFFER_SIZE];
         }
     }
 }
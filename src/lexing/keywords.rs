@@ -6,7 +6,7 @@ use crate::lexing::token::TokenKind::*;
 
 use super::token::TokenKind;
 
-#[derive(PartialEq)]
+#[derive(Debug, PartialEq)]
 pub enum Keyword {
     And,
     Or,
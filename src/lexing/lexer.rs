@@ -6,9 +6,13 @@ use std::{
     },
 };
 
+use unicode_ident::{is_xid_continue, is_xid_start};
+
 use super::keywords::Keyword;
 use super::token::{Token, TokenKind};
+use crate::common::errors::CompilerError;
 use crate::common::literal::Literal;
+use crate::common::span::Span;
 use crate::lexing::symbols::Symbol::*;
 use crate::lexing::token::TokenKind::*;
 
@@ -16,71 +20,201 @@ pub(crate) struct Lexer {
     file_chunk_receiver: Receiver<VecDeque<u8>>,
     token_transmitter: Sender<Token>,
     current_chunks: VecDeque<u8>,
-    line: usize,
-    column: usize,
+    // characters read ahead and given back, the last one is read first
+    unread: Vec<char>,
+    // positions before the last characters read, so that unreading steps back
+    history: VecDeque<Position>,
+    // start of the string and brace depth of every `{expression}` open inside an interpolated string
+    interpolations: Vec<(Position, usize)>,
+    file_id: u32,
+    position: Position,
+    // every lexical error found so far, lexing goes on after them
+    errors: Vec<CompilerError>,
+    // brackets open around the current character, `$` for the `{` of an interpolation
+    brackets: Vec<char>,
+    // whether the last token sent can be the end of a statement
+    ends_statement: bool,
+    // lossless mode only: the text read since the end of the last token,
+    // where that text starts, and the last token, held back until its trailing trivia is read
+    source: Option<String>,
+    source_start: u32,
+    held: Option<Token>,
+}
+
+// where the next character starts
+#[derive(Clone, Copy)]
+struct Position {
+    byte: u32,
+    // bytes of the decoded text, it falls behind `byte` when bytes that are not utf-8 are skipped
+    decoded: u32,
+    line: u32,
+    column: u32,
 }
 
+// the lexer never looks further ahead than this
+const HISTORY_SIZE: usize = 8;
+// the `#`s before the quote of a raw string are read ahead, along with the character after them
+const MAX_RAW_STRING_HASHES: usize = HISTORY_SIZE - 1;
+
 impl Lexer {
     pub(crate) fn new(
         file_chunk_receiver: Receiver<VecDeque<u8>>,
         token_transmitter: Sender<Token>,
+        file_id: u32,
     ) -> Self {
         Self {
             file_chunk_receiver,
             token_transmitter,
             current_chunks: VecDeque::default(),
-            column: 0,
-            line: 0,
+            unread: Vec::new(),
+            history: VecDeque::with_capacity(HISTORY_SIZE),
+            interpolations: Vec::new(),
+            file_id,
+            position: Position {
+                byte: 0,
+                decoded: 0,
+                line: 1,
+                column: 1,
+            },
+            errors: Vec::new(),
+            brackets: Vec::new(),
+            ends_statement: false,
+            source: None,
+            source_start: 0,
+            held: None,
         }
     }
 
+    // every token keeps its text and the whitespace and comments around it
+    pub(crate) fn lossless(mut self) -> Self {
+        self.source = Some(String::new());
+        self
+    }
+
     pub(crate) fn next(&mut self) -> char {
-        let val = if !self.current_chunks.is_empty() {
-            self.current_chunks.pop_front().unwrap_or(0) as char
-        } else if let Ok(mut chunk) = self.file_chunk_receiver.recv() {
-            let ch = chunk.pop_front();
-            self.current_chunks = chunk;
-            ch.unwrap_or(0) as char
-        } else {
-            '\0'
+        let val = match self.unread.pop() {
+            Some(ch) => ch,
+            None => self.read(),
         };
 
+        if self.history.len() == HISTORY_SIZE {
+            self.history.pop_front();
+        }
+        self.history.push_back(self.position);
         if val != '\0' {
-            self.column += 1;
+            self.position.byte += val.len_utf8() as u32;
+            self.position.decoded += val.len_utf8() as u32;
+            if let Some(source) = &mut self.source {
+                source.push(val);
+            }
+            if val == '\n' {
+                self.position.line += 1;
+                self.position.column = 1;
+            } else {
+                self.position.column += 1;
+            }
         }
         val
     }
 
-    pub(crate) fn lex(mut self) {
-        let mut temp = None;
-        loop {
-            let mut current = if let Some(ch) = temp {
-                temp = None;
-                ch
-            } else {
-                self.next()
+    // decodes the next character of the source, bytes that are not utf-8 are skipped.
+    // a run of them is reported once, every invalid sequence in it takes up a column
+    fn read(&mut self) -> char {
+        let mut invalid: Option<Span> = None;
+        let ch = loop {
+            let Some(first) = self.next_byte() else {
+                break '\0';
             };
+            let width = match first {
+                0x00..=0x7F => break first as char,
+                0xC2..=0xDF => 2,
+                0xE0..=0xEF => 3,
+                0xF0..=0xF4 => 4,
+                _ => 1,
+            };
+            let mut bytes = vec![first];
+            while bytes.len() < width {
+                match self.next_byte() {
+                    Some(byte @ 0x80..=0xBF) => bytes.push(byte),
+                    Some(byte) => {
+                        self.current_chunks.push_front(byte);
+                        break;
+                    }
+                    None => break,
+                }
+            }
+            if let Some(ch) = std::str::from_utf8(&bytes)
+                .ok()
+                .and_then(|decoded| decoded.chars().next())
+            {
+                break ch;
+            }
+            let span = invalid.get_or_insert(Span::new(
+                self.file_id,
+                self.position.byte,
+                self.position.byte,
+                self.position.line,
+                self.position.column,
+            ));
+            self.position.byte += bytes.len() as u32;
+            self.position.column += 1;
+            span.end_byte = self.position.byte;
+        };
+        if let Some(span) = invalid {
+            self.errors.push(CompilerError::InvalidUtf8Character(span));
+        }
+        ch
+    }
+
+    fn next_byte(&mut self) -> Option<u8> {
+        while self.current_chunks.is_empty() {
+            self.current_chunks = self.file_chunk_receiver.recv().ok()?;
+        }
+        self.current_chunks.pop_front()
+    }
+
+    // a malformed token is sent as an `Error` token and lexing goes on after it,
+    // so that every lexical error of the source is returned at once
+    pub(crate) fn lex(mut self) -> Result<(), CompilerError> {
+        loop {
+            let start = self.position;
+            let mut current = self.next();
 
             if current == '\0' {
-                let eof = Token::new(TokenKind::EndOfFile, self.line, self.column);
-                self.token_transmitter.send(eof).unwrap();
-                return;
+                self.send(EndOfFile, start);
+                return CompilerError::from_errors(self.errors);
             }
 
-            let token = match current {
-                '\r' => {
-                    if self.next() != '\n' {
-                        panic!("Expected '\\n' after '\\r'")
+            let kind = match current {
+                '\r' | '\n' => {
+                    if current == '\r' && !self.followed_by('\n') {
+                        let span = self.span_from(start);
+                        self.report(CompilerError::InvalidCharacter(current, span));
+                        continue;
                     }
-                    self.line += 1;
-                    // let token = Token::new(NewLine, self.increment_line(), self.column);
-                    // token
-                    continue;
+                    if !self.ends_line() {
+                        continue;
+                    }
+                    NewLine
                 }
-                '\n' => {
-                    self.line += 1;
-                    // let token = Token::new(NewLine, self.increment_line(), self.column);
-                    // token
+                '#' => {
+                    current = self.next();
+                    if current == '[' {
+                        if let Err(err) = self.skip_block_comment(start) {
+                            self.report(err);
+                        }
+                        // a comment over several lines separates statements like a new line,
+                        // the comment itself stays trivia
+                        if self.position.line > start.line && self.ends_line() {
+                            self.send(NewLine, self.position);
+                        }
+                    } else {
+                        while current != '\n' && current != '\0' {
+                            current = self.next();
+                        }
+                        // the new line still has to be counted
+                        self.unread(current);
+                    }
                     continue;
                 }
                 ch if ch.is_ascii_whitespace() => {
@@ -88,100 +222,650 @@ impl Lexer {
                     // token
                     continue;
                 }
-                ch if ch.is_ascii_digit() => {
-                    let mut number_as_string = String::from(ch);
-                    current = self.next();
-                    while current.is_ascii_digit() {
-                        number_as_string.push(current);
-                        current = self.next();
-                    }
-                    if current == '.' {
-                        number_as_string.push('.');
-                        current = self.next();
-                        while current.is_ascii_digit() {
-                            number_as_string.push(current);
-                            current = self.next();
+                ch if ch.is_ascii_digit() => match self.lex_number(ch, start) {
+                    Ok(kind) => kind,
+                    Err(err) => self.report(err),
+                },
+                'r' => match self.raw_string_hashes() {
+                    Some(hashes) if hashes > MAX_RAW_STRING_HASHES => {
+                        // the string is still read to its end, so that lexing goes on after it
+                        if self.followed_by('\"') {
+                            self.lex_raw_string(hashes, start);
                         }
+                        let span = self.span_from(start);
+                        self.report(CompilerError::TooManyRawStringHashes(
+                            MAX_RAW_STRING_HASHES,
+                            span,
+                        ))
                     }
-                    temp = Some(current);
-                    let number: f64 = match number_as_string.parse() {
-                        Ok(number) => number,
-                        Err(_) => {
-                            panic!("Invalid Number")
-                        }
-                    };
-                    Token::new(
-                        TokenKind::Literal(Literal::from(number)),
-                        self.line,
-                        self.column,
-                    )
-                }
-                ch if ch.is_alphabetic() || ch == '_' => {
-                    let mut word = String::from(ch);
+                    Some(hashes) => self.lex_raw_string(hashes, start),
+                    None => self.lex_word(current),
+                },
+                ch if is_xid_start(ch) || ch == '_' => self.lex_word(ch),
+                '\'' => self.lex_string(current, start),
+                '\"' => {
+                    if !self.followed_by('\"') {
+                        self.lex_string(current, start)
+                    } else if self.followed_by('\"') {
+                        self.lex_multi_line_string(start)
+                    } else {
+                        // ""
+                        Literal(Literal::from(Arc::new(String::new())))
+                    }
+                }
+                '+' => Symbol(if self.followed_by('=') {
+                    PlusEquals
+                } else {
+                    Plus
+                }),
+                '-' => Symbol(if self.followed_by('=') {
+                    MinusEquals
+                } else {
+                    Minus
+                }),
+                '*' => Symbol(if self.followed_by('*') {
+                    if self.followed_by('=') {
+                        StarStarEquals
+                    } else {
+                        StarStar
+                    }
+                } else if self.followed_by('=') {
+                    AsteriskEquals
+                } else {
+                    Asterisk
+                }),
+                '/' => Symbol(if self.followed_by('=') {
+                    SlashEquals
+                } else {
+                    Slash
+                }),
+                '%' => Symbol(if self.followed_by('=') {
+                    PercentEquals
+                } else {
+                    Percent
+                }),
+                '(' => {
+                    self.brackets.push(current);
+                    Symbol(OpenParanthesis)
+                }
+                ')' => {
+                    self.brackets.pop();
+                    Symbol(CloseParanthesis)
+                }
+                '$' => {
+                    if !self.followed_by('\"') {
+                        let span = self.span_from(start);
+                        self.report(CompilerError::InvalidCharacter(current, span))
+                    } else {
+                        self.send(InterpolationStart, start);
+                        self.lex_string_fragment(start);
+                        continue;
+                    }
+                }
+                '{' => {
+                    if let Some((_, depth)) = self.interpolations.last_mut() {
+                        *depth += 1;
+                    }
+                    self.brackets.push(current);
+                    Symbol(OpenCurlyBracket)
+                }
+                '}' => match self.interpolations.last_mut() {
+                    Some((string_start, 0)) => {
+                        // end of an `{expression}`, back to the text of the string
+                        let string_start = *string_start;
+                        self.interpolations.pop();
+                        self.brackets.pop();
+                        self.send(Symbol(CloseCurlyBracket), start);
+                        self.lex_string_fragment(string_start);
+                        continue;
+                    }
+                    Some((_, depth)) => {
+                        *depth -= 1;
+                        self.brackets.pop();
+                        Symbol(CloseCurlyBracket)
+                    }
+                    None => {
+                        self.brackets.pop();
+                        Symbol(CloseCurlyBracket)
+                    }
+                },
+                '[' => {
+                    self.brackets.push(current);
+                    Symbol(OpenSquareBracket)
+                }
+                ']' => {
+                    self.brackets.pop();
+                    Symbol(CloseSquareBracket)
+                }
+                '=' => Symbol(if self.followed_by('=') {
+                    EqualsEquals
+                } else if self.followed_by('>') {
+                    FatArrow
+                } else {
+                    Equals
+                }),
+                '<' => Symbol(if self.followed_by('=') {
+                    LessThanEquals
+                } else {
+                    LessThan
+                }),
+                '>' => Symbol(if self.followed_by('=') {
+                    GreaterThanEquals
+                } else {
+                    GreaterThan
+                }),
+                '!' => Symbol(if self.followed_by('=') {
+                    ExclamationEquals
+                } else {
+                    Exclamation
+                }),
+                ',' => Symbol(Comma),
+                ':' => Symbol(Colon),
+                '.' => Symbol(if !self.followed_by('.') {
+                    Dot
+                } else if self.followed_by('=') {
+                    DotDotEquals
+                } else {
+                    DotDot
+                }),
+                ';' => Symbol(Semicolon),
+                ch => {
+                    let span = self.span_from(start);
+                    self.report(CompilerError::InvalidCharacter(ch, span))
+                }
+            };
+
+            if !self.send(kind, start) {
+                // parser stopped listening
+                return CompilerError::from_errors(self.errors);
+            }
+        }
+    }
+
+    // a new line ends the statement before it when that statement is complete,
+    // unless it is inside parentheses or square brackets
+    fn ends_line(&self) -> bool {
+        self.ends_statement && matches!(self.brackets.last(), None | Some('{'))
+    }
+
+    // remembers the error and returns the kind of token that stands in for the malformed one
+    fn report(&mut self, err: CompilerError) -> TokenKind {
+        self.errors.push(err);
+        Error
+    }
+
+    // identifiers and keywords
+    fn lex_word(&mut self, first: char) -> TokenKind {
+        let mut word = String::from(first);
+        let mut current = self.next();
+        while is_xid_continue(current) {
+            word.push(current);
+            current = self.next();
+        }
+        self.unread(current);
+        Keyword::get_keyword_kind(&word)
+    }
+
+    // reads a string whose opening quote is already read
+    fn lex_string(&mut self, quote: char, start: Position) -> TokenKind {
+        let errors = self.errors.len();
+        let mut word = String::new();
+        loop {
+            let before = self.position;
+            match self.next() {
+                '\0' => {
+                    let span = self.span_from(start);
+                    return self.report(CompilerError::UnterminatedString(span));
+                }
+                '\\' => match self.lex_escape_sequence(before) {
+                    Ok(ch) => word.push(ch),
+                    Err(err) => self.errors.push(err),
+                },
+                ch if ch == quote => break,
+                ch => word.push(ch),
+            }
+        }
+        if self.errors.len() > errors {
+            Error
+        } else {
+            Literal(Literal::from(Arc::new(word)))
+        }
+    }
+
+    // """ text """, escapes work as in other strings. the new line after the opening quotes,
+    // the line of the closing quotes and the indentation common to every line are left out
+    fn lex_multi_line_string(&mut self, start: Position) -> TokenKind {
+        let errors = self.errors.len();
+        // every character with whether it came from an escape sequence
+        let mut text = Vec::new();
+        loop {
+            let before = self.position;
+            match self.next() {
+                '\0' => {
+                    let span = self.span_from(start);
+                    return self.report(CompilerError::UnterminatedString(span));
+                }
+                '\"' => {
+                    let mut quotes = 1;
+                    while quotes < 3 && self.followed_by('\"') {
+                        quotes += 1;
+                    }
+                    if quotes == 3 {
+                        break;
+                    }
+                    text.extend(std::iter::repeat_n(('\"', false), quotes));
+                }
+                '\\' => match self.lex_escape_sequence(before) {
+                    Ok(ch) => text.push((ch, true)),
+                    Err(err) => self.errors.push(err),
+                },
+                '\r' if self.followed_by('\n') => text.push(('\n', false)),
+                ch => text.push((ch, false)),
+            }
+        }
+        if self.errors.len() > errors {
+            Error
+        } else {
+            Literal(Literal::from(Arc::new(strip_indentation(&text))))
+        }
+    }
+
+    // takes the `#`s and the quote that start a raw string after an `r`,
+    // returns how many `#`s there were. nothing is taken when it is not a raw string.
+    // more `#`s than the history can give back are all taken, without the quote after them
+    fn raw_string_hashes(&mut self) -> Option<usize> {
+        let mut hashes = 0;
+        while self.followed_by('#') {
+            hashes += 1;
+            if hashes > MAX_RAW_STRING_HASHES {
+                while self.followed_by('#') {
+                    hashes += 1;
+                }
+                return Some(hashes);
+            }
+        }
+        if self.followed_by('\"') {
+            return Some(hashes);
+        }
+        for _ in 0..hashes {
+            self.unread('#');
+        }
+        None
+    }
+
+    // r"text" or r#"text"#, escapes are not processed. the string ends at a quote
+    // followed by as many `#`s as it started with
+    fn lex_raw_string(&mut self, hashes: usize, start: Position) -> TokenKind {
+        let mut text = String::new();
+        loop {
+            match self.next() {
+                '\0' => {
+                    let span = self.span_from(start);
+                    return self.report(CompilerError::UnterminatedString(span));
+                }
+                '\"' => {
+                    let mut closing = 0;
+                    while closing < hashes && self.followed_by('#') {
+                        closing += 1;
+                    }
+                    if closing == hashes {
+                        break;
+                    }
+                    text.push('\"');
+                    text.extend(std::iter::repeat_n('#', closing));
+                }
+                ch => text.push(ch),
+            }
+        }
+        Literal(Literal::from(Arc::new(text)))
+    }
+
+    // gives back the last character read
+    fn unread(&mut self, ch: char) {
+        self.unread.push(ch);
+        if let Some(position) = self.history.pop_back() {
+            self.position = position;
+        }
+        if let Some(source) = &mut self.source {
+            source.truncate((self.position.decoded - self.source_start) as usize);
+        }
+    }
+
+    // takes the next character only if it is the expected one
+    fn followed_by(&mut self, expected: char) -> bool {
+        let next = self.next();
+        if next == expected {
+            true
+        } else {
+            self.unread(next);
+            false
+        }
+    }
+
+    fn span(&self, start: Position, end: Position) -> Span {
+        Span::new(self.file_id, start.byte, end.byte, start.line, start.column)
+    }
+
+    // from the start up to the last character read
+    fn span_from(&self, start: Position) -> Span {
+        self.span(start, self.position)
+    }
+
+    // integers: 42, 1_000, 0xFF, 0b1010, 0o17
+    // floats: 3.14, 1.5e-3, 2E10
+    fn lex_number(&mut self, first: char, start: Position) -> Result<TokenKind, CompilerError> {
+        let mut number_as_string = String::from(first);
+        let mut current = self.next();
+
+        if first == '0' && matches!(current, 'x' | 'X' | 'b' | 'B' | 'o' | 'O') {
+            let radix = match current.to_ascii_lowercase() {
+                'x' => 16,
+                'b' => 2,
+                _ => 8,
+            };
+            number_as_string.push(current);
+            let mut digits = String::new();
+            current = self.next();
+            // every letter is taken so that `0b12` is an invalid number, not `0b1` and `2`
+            while current.is_ascii_alphanumeric() || current == '_' {
+                number_as_string.push(current);
+                if current != '_' {
+                    digits.push(current);
+                }
+                current = self.next();
+            }
+            self.unread(current);
+            return match i128::from_str_radix(&digits, radix) {
+                Ok(number) => Ok(Literal(Literal::from(number))),
+                Err(_) => Err(CompilerError::InvalidNumber(
+                    number_as_string,
+                    self.span_from(start),
+                )),
+            };
+        }
+
+        let mut is_float = false;
+        current = self.lex_digits(&mut number_as_string, current);
+        if current == '.' {
+            // `1..10` and `1.method` are not fractions
+            let after = self.next();
+            if after.is_ascii_digit() {
+                is_float = true;
+                number_as_string.push('.');
+                current = self.lex_digits(&mut number_as_string, after);
+            } else {
+                self.unread(after);
+            }
+        }
+        if current == 'e' || current == 'E' {
+            let mut exponent = String::from(current);
+            let mut after = self.next();
+            let sign = if after == '+' || after == '-' {
+                exponent.push(after);
+                let sign = after;
+                after = self.next();
+                Some(sign)
+            } else {
+                None
+            };
+            if after.is_ascii_digit() {
+                is_float = true;
+                number_as_string.push_str(&exponent);
+                current = self.lex_digits(&mut number_as_string, after);
+            } else {
+                self.unread(after);
+                if let Some(sign) = sign {
+                    self.unread(sign);
+                }
+            }
+        }
+        self.unread(current);
+
+        let digits = number_as_string.replace('_', "");
+        let literal = if is_float {
+            match digits.parse::<f64>() {
+                Ok(number) if number.is_finite() => Literal::from(number),
+                _ => {
+                    return Err(CompilerError::InvalidNumber(
+                        number_as_string,
+                        self.span_from(start),
+                    ))
+                }
+            }
+        } else {
+            match digits.parse::<i128>() {
+                Ok(number) => Literal::from(number),
+                Err(_) => {
+                    return Err(CompilerError::InvalidNumber(
+                        number_as_string,
+                        self.span_from(start),
+                    ))
+                }
+            }
+        };
+        Ok(Literal(literal))
+    }
+
+    // reads digits and `_` separators into `number`, returns the first character after them
+    fn lex_digits(&mut self, number: &mut String, mut current: char) -> char {
+        while current.is_ascii_digit() || current == '_' {
+            number.push(current);
+            current = self.next();
+        }
+        current
+    }
+
+    // sends a token that started at `start` and ends at the last character read,
+    // returns false once the parser stopped listening
+    fn send(&mut self, kind: TokenKind, start: Position) -> bool {
+        self.transmit(kind, start, self.position)
+    }
+
+    fn transmit(&mut self, kind: TokenKind, start: Position, end: Position) -> bool {
+        self.ends_statement = kind.can_end_statement();
+        let mut token = Token::new(kind, self.span(start, end));
+        let Some(source) = &mut self.source else {
+            return self.token_transmitter.send(token).is_ok();
+        };
+
+        let token_start = (start.decoded - self.source_start) as usize;
+        let token_end = (end.decoded - self.source_start) as usize;
+        let mut trivia = &source[..token_start];
+        if let Some(mut held) = self.held.take() {
+            // trivia up to the end of the line belongs to the token before
+            let line_end = trivia.find(['\r', '\n']).unwrap_or(trivia.len());
+            held.trailing_trivia = trivia[..line_end].to_string();
+            trivia = &trivia[line_end..];
+            if self.token_transmitter.send(held).is_err() {
+                return false;
+            }
+        }
+        token.leading_trivia = trivia.to_string();
+        token.text = source[token_start..token_end].to_string();
+        source.drain(..token_end);
+        self.source_start = end.decoded;
+
+        if TokenKind::EndOfFile == token.kind {
+            self.token_transmitter.send(token).is_ok()
+        } else {
+            self.held = Some(token);
+            true
+        }
+    }
+
+    // reads the text of an interpolated string up to its next `{expression}` or closing quote
+    fn lex_string_fragment(&mut self, string_start: Position) {
+        let start = self.position;
+        let mut fragment = String::new();
+        loop {
+            let before = self.position;
+            match self.next() {
+                '\0' => {
+                    let span = self.span_from(string_start);
+                    let kind = self.report(CompilerError::UnterminatedString(span));
+                    self.send(kind, start);
+                    return;
+                }
+                '\"' => {
+                    self.send_fragment(fragment, start, before);
+                    self.send(InterpolationEnd, before);
+                    return;
+                }
+                '{' => {
+                    if self.followed_by('{') {
+                        fragment.push('{');
+                        continue;
+                    }
+                    self.send_fragment(fragment, start, before);
+                    self.send(Symbol(OpenCurlyBracket), before);
+                    self.brackets.push('$');
+                    self.interpolations.push((string_start, 0));
+                    return;
+                }
+                '}' => {
+                    // `}}` stands for one `}`
+                    self.followed_by('}');
+                    fragment.push('}');
+                }
+                '\\' => match self.lex_escape_sequence(before) {
+                    Ok(ch) => fragment.push(ch),
+                    Err(err) => self.errors.push(err),
+                },
+                ch => fragment.push(ch),
+            }
+        }
+    }
+
+    fn send_fragment(&mut self, fragment: String, start: Position, end: Position) {
+        if !fragment.is_empty() {
+            self.transmit(StringFragment(fragment), start, end);
+        }
+    }
+
+    // reads the escape sequence after a `\` inside a string and returns the character it stands for
+    fn lex_escape_sequence(&mut self, start: Position) -> Result<char, CompilerError> {
+        let escaped = match self.next() {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '0' => '\0',
+            '\\' => '\\',
+            '\"' => '\"',
+            '\'' => '\'',
+            'u' => {
+                // \u{1F600}
+                let mut sequence = String::from("\\u");
+                let mut current = self.next();
+                if current != '{' {
+                    // it may be the closing quote, the string goes on from it
+                    self.unread(current);
+                    return Err(CompilerError::InvalidEscapeSequence(
+                        sequence,
+                        self.span_from(start),
+                    ));
+                }
+                sequence.push(current);
+                let mut hex = String::new();
+                current = self.next();
+                while current.is_ascii_hexdigit() {
+                    hex.push(current);
                     current = self.next();
-                    while current.is_ascii_alphanumeric() || current == '_' {
-                        word.push(current);
-                        current = self.next();
+                }
+                sequence.push_str(&hex);
+                if current == '}' {
+                    sequence.push(current);
+                } else {
+                    self.unread(current);
+                }
+                if current != '}' || hex.is_empty() || hex.len() > 6 {
+                    return Err(CompilerError::InvalidEscapeSequence(
+                        sequence,
+                        self.span_from(start),
+                    ));
+                }
+                match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    Some(ch) => ch,
+                    None => {
+                        return Err(CompilerError::InvalidEscapeSequence(
+                            sequence,
+                            self.span_from(start),
+                        ))
                     }
-                    temp = Some(current);
-                    let keyword = Keyword::get_keyword_kind(&word);
-                    Token::new(keyword, self.line, self.column)
                 }
-                '\'' => {
+            }
+            other => {
+                return Err(CompilerError::InvalidEscapeSequence(
+                    format!("\\{other}"),
+                    self.span_from(start),
+                ))
+            }
+        };
+        Ok(escaped)
+    }
+
+    // skips a block comment `#[ ... ]#` whose `#[` is already read, block comments can be nested
+    fn skip_block_comment(&mut self, start: Position) -> Result<(), CompilerError> {
+        let mut depth = 1;
+        let mut current = self.next();
+        loop {
+            match current {
+                '\0' => return Err(CompilerError::UnterminatedComment(self.span_from(start))),
+                '#' => {
                     current = self.next();
-                    let mut word = String::new();
-                    while current != '\'' && current != '\0' {
-                        word.push(current);
-                        current = self.next();
-                    }
-                    if current != '\'' {
-                        panic!("Unterminated string")
-                    };
-                    Token::new(
-                        Literal(Literal::from(Arc::new(word.to_string()))),
-                        self.line,
-                        self.column,
-                    )
+                    if current != '[' {
+                        continue;
+                    }
+                    depth += 1;
                 }
-                '\"' => {
+                ']' => {
                     current = self.next();
-                    let mut word = String::new();
-                    while current != '\"' && current != '\0' {
-                        word.push(current);
-                        current = self.next();
-                    }
-                    if current != '\"' {
-                        panic!("Unterminated string")
-                    };
-                    Token::new(
-                        TokenKind::Literal(Literal::from(Arc::new(word.to_string()))),
-                        self.line,
-                        self.column,
-                    )
-                }
-                '+' => Token::new(Symbol(Plus), self.line, self.column),
-                '-' => Token::new(Symbol(Minus), self.line, self.column),
-                '*' => Token::new(Symbol(Asterisk), self.line, self.column),
-                '/' => Token::new(Symbol(Slash), self.line, self.column),
-                '%' => Token::new(Symbol(Percent), self.line, self.column),
-                '(' => Token::new(Symbol(OpenParanthesis), self.line, self.column),
-                ')' => Token::new(Symbol(CloseParanthesis), self.line, self.column),
-                '{' => Token::new(Symbol(OpenCurlyBracket), self.line, self.column),
-                '}' => Token::new(Symbol(CloseCurlyBracket), self.line, self.column),
-                '[' => Token::new(Symbol(OpenSquareBracket), self.line, self.column),
-                ']' => Token::new(Symbol(CloseSquareBracket), self.line, self.column),
-                '=' => Token::new(Symbol(Equals), self.line, self.column),
-                '<' => Token::new(Symbol(LessThan), self.line, self.column),
-                '>' => Token::new(Symbol(GreaterThan), self.line, self.column),
-                '!' => Token::new(Symbol(Exclamation), self.line, self.column),
-                ',' => Token::new(Symbol(Comma), self.line, self.column),
-                ':' => Token::new(Symbol(Colon), self.line, self.column),
-                _ => {
-                    panic!("invalid character")
+                    if current != '#' {
+                        continue;
+                    }
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(());
+                    }
                 }
-            };
+                _ => {}
+            }
+            current = self.next();
+        }
+    }
+}
 
-            self.token_transmitter.send(token).unwrap();
+// removes the indentation common to the lines of a multi-line string, along with a first line
+// and a last line that are only whitespace. characters from escape sequences are never
+// taken for indentation or line breaks
+fn strip_indentation(text: &[(char, bool)]) -> String {
+    let is_space = |&(ch, escaped): &(char, bool)| !escaped && (ch == ' ' || ch == '\t');
+    let mut lines: Vec<&[(char, bool)]> = text
+        .split(|&(ch, escaped)| ch == '\n' && !escaped)
+        .collect();
+    if lines.len() == 1 {
+        return text.iter().map(|(ch, _)| ch).collect();
+    }
+    if lines[0].iter().all(is_space) {
+        lines.remove(0);
+    }
+    // the indentation of the closing quotes counts as well
+    let mut closing_indentation = None;
+    if let Some(last) = lines.last() {
+        if last.iter().all(is_space) {
+            closing_indentation = Some(last.len());
+            lines.pop();
         }
     }
+    let indentation = lines
+        .iter()
+        .filter(|line| !line.iter().all(is_space))
+        .map(|line| line.iter().take_while(|ch| is_space(ch)).count())
+        .chain(closing_indentation)
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| line.iter().skip(indentation).map(|(ch, _)| ch).collect())
+        .collect::<Vec<String>>()
+        .join("\n")
 }
@@ -1,3 +1,10 @@
+use std::io::Read;
+use std::sync::mpsc;
+use std::thread;
+
+use crate::common::errors::CompilerError;
+use token::{Token, TokenKind};
+
 pub mod file_reader;
 pub(crate) mod keywords;
 pub(crate) mod lexer;
@@ -6,3 +13,41 @@ pub(crate) mod token;
 
 pub(crate) use file_reader::FileReader;
 pub(crate) use lexer::Lexer;
+
+/// Splits the source into tokens without losing any of it.
+///
+/// Concatenating the `leading_trivia`, `text` and `trailing_trivia` of every token,
+/// up to and including the `EndOfFile` token, gives back the source byte for byte
+/// as long as it is valid UTF-8.
+///
+/// Lexical errors do not stop it: a malformed token is an `Error` token that keeps its text,
+/// and the errors are returned next to the tokens, in the order they appear in the source.
+pub fn tokenize(source: impl Read + Send + 'static) -> (Vec<Token>, Vec<CompilerError>) {
+    let (file_chunk_transmitter, file_chunk_receiver) = mpsc::channel();
+    let (token_transmitter, token_receiver) = mpsc::channel();
+    let file_reader = FileReader::new(source, file_chunk_transmitter);
+    let lexer = Lexer::new(file_chunk_receiver, token_transmitter, 0).lossless();
+
+    thread::scope(move |scope| {
+        let lexing = scope.spawn(move || lexer.lex());
+        let read = file_reader.read();
+        let mut tokens = Vec::new();
+        for token in token_receiver {
+            let is_end = TokenKind::EndOfFile == token.kind;
+            tokens.push(token);
+            if is_end {
+                break;
+            }
+        }
+        let lexed = lexing
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+        let mut errors: Vec<CompilerError> = [read.err(), lexed.err()]
+            .into_iter()
+            .flatten()
+            .flat_map(CompilerError::into_errors)
+            .collect();
+        errors.sort_by_key(|err| err.span().map(|span| span.start_byte));
+        (tokens, errors)
+    })
+}
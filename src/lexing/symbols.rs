@@ -19,7 +19,22 @@ pub enum Symbol {
     LessThan,
     Comma,
     Colon,
+    Dot,
+    DotDot,
+    DotDotEquals,
     Semicolon,
+    EqualsEquals,
+    ExclamationEquals,
+    LessThanEquals,
+    GreaterThanEquals,
+    PlusEquals,
+    MinusEquals,
+    AsteriskEquals,
+    SlashEquals,
+    PercentEquals,
+    StarStar,
+    StarStarEquals,
+    FatArrow,
 }
 
 impl Display for Symbol {
@@ -43,7 +58,22 @@ impl Display for Symbol {
             CloseCurlyBracket => "}",
             Comma => ",",
             Colon => ":",
+            Dot => ".",
+            DotDot => "..",
+            DotDotEquals => "..=",
             Semicolon => ";",
+            EqualsEquals => "==",
+            ExclamationEquals => "!=",
+            LessThanEquals => "<=",
+            GreaterThanEquals => ">=",
+            PlusEquals => "+=",
+            MinusEquals => "-=",
+            AsteriskEquals => "*=",
+            SlashEquals => "/=",
+            PercentEquals => "%=",
+            StarStar => "**",
+            StarStarEquals => "**=",
+            FatArrow => "=>",
             // TODO: add bitwise operators &, |, ^
         };
         write!(f, "{}", text)
@@ -1,18 +1,34 @@
 use std::fmt::Display;
 
 use crate::common::literal::Literal;
+use crate::common::span::Span;
 
 use super::{keywords::Keyword, symbols::Symbol};
 
-pub(crate) struct Token {
-    pub(crate) kind: TokenKind,
-    pub(crate) line: usize,
-    pub(crate) column: usize,
+/// A token of the source.
+///
+/// The text and trivia are only kept by [`tokenize`](crate::tokenize), they are empty otherwise.
+#[derive(Debug)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+    /// Whitespace, new lines and comments before the token.
+    pub leading_trivia: String,
+    /// The token as it is written in the source.
+    pub text: String,
+    /// Whitespace and comments after the token, up to the end of its line.
+    pub trailing_trivia: String,
 }
 
 impl Token {
-    pub(crate) fn new(kind: TokenKind, line: usize, column: usize) -> Self {
-        Self { kind, line, column }
+    pub(crate) fn new(kind: TokenKind, span: Span) -> Self {
+        Self {
+            kind,
+            span,
+            leading_trivia: String::new(),
+            text: String::new(),
+            trailing_trivia: String::new(),
+        }
     }
 }
 
@@ -22,15 +38,43 @@ impl Display for Token {
     }
 }
 
-#[derive(PartialEq)]
+#[derive(Debug, PartialEq)]
 pub enum TokenKind {
     Literal(Literal),
     Keyword(Keyword),
     Symbol(Symbol),
     Identifier(String),
+    // $"text {expression} text" is lexed as
+    // InterpolationStart StringFragment { expression tokens } StringFragment InterpolationEnd
+    InterpolationStart,
+    StringFragment(String),
+    InterpolationEnd,
+    // ends a statement, only sent where the statement before it is complete
+    NewLine,
+    // stands in for a malformed token, the lexer reports why
+    Error,
     EndOfFile,
 }
 
+impl TokenKind {
+    // tokens after which a new line ends the statement
+    pub(crate) fn can_end_statement(&self) -> bool {
+        matches!(
+            self,
+            Self::Literal(_)
+                | Self::Identifier(_)
+                | Self::InterpolationEnd
+                | Self::Error
+                | Self::Symbol(Symbol::CloseParanthesis)
+                | Self::Symbol(Symbol::CloseSquareBracket)
+                | Self::Symbol(Symbol::CloseCurlyBracket)
+                | Self::Keyword(Keyword::Return)
+                | Self::Keyword(Keyword::Break)
+                | Self::Keyword(Keyword::Skip)
+        )
+    }
+}
+
 impl Display for TokenKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         use TokenKind::*;
@@ -39,6 +83,11 @@ impl Display for TokenKind {
             Keyword(a) => write!(f, "{}", a),
             Symbol(a) => write!(f, "{}", a),
             Identifier(a) => write!(f, "{}", a),
+            InterpolationStart => write!(f, "$\""),
+            StringFragment(a) => write!(f, "{}", a),
+            InterpolationEnd => write!(f, "\""),
+            NewLine => write!(f, "new line"),
+            Error => write!(f, "invalid token"),
             EndOfFile => write!(f, "end of the file"),
         }
     }
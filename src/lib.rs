@@ -0,0 +1,17 @@
+pub(crate) mod common;
+pub(crate) mod evaluating;
+mod interpreter;
+pub(crate) mod lexing;
+pub(crate) mod parsing;
+pub mod repl;
+pub(crate) mod resolving;
+
+pub use common::datatypes::DataType;
+pub use common::errors::CompilerError;
+pub use common::literal::Literal;
+pub use common::span::Span;
+pub use interpreter::Interpreter;
+pub use lexing::keywords::Keyword;
+pub use lexing::symbols::Symbol;
+pub use lexing::token::{Token, TokenKind};
+pub use lexing::tokenize;
@@ -1,34 +1,66 @@
-pub(crate) mod common;
-pub(crate) mod evaluating;
-pub(crate) mod lexing;
-pub(crate) mod parsing;
+use prolang::{repl, CompilerError, Interpreter};
+use std::io::{Cursor, Read};
+use std::process::ExitCode;
 
-use evaluating::{evaluator::Evaluator, global::Global};
-use lexing::{FileReader, Lexer};
-use parsing::Parser;
-use std::{sync::Arc, thread};
+const USAGE: &str = "usage:
+    prolang run <file> [args...]   run a program
+    prolang check <file>           lex and parse a program without running it
+    prolang eval -e <code>         run the given code
+    prolang repl                   start an interactive session
 
-fn main() {
-    // Global fields and functions
-    let global = Global::new();
-    // channels
-    let (file_chunk_transmitter, file_chunk_receiver) = std::sync::mpsc::channel();
-    let (token_transmitter, token_receiver) = std::sync::mpsc::channel();
-    let (statement_transmitter, statement_receiver) = std::sync::mpsc::channel();
+use `-` as <file> to read the program from stdin";
 
-    let file_reader = FileReader::new("app.prolang", file_chunk_transmitter);
-    let lexer = Lexer::new(file_chunk_receiver, token_transmitter);
-    let parser = Parser::new(
-        token_receiver,
-        statement_transmitter,
-        Arc::clone(&global.block),
-    );
-    let evaluator = Evaluator::new(statement_receiver, global);
+fn main() -> ExitCode {
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    match arguments.first().map(String::as_str) {
+        Some("run") if arguments.len() >= 2 => {
+            let file_name = &arguments[1];
+            match open_source(file_name) {
+                Ok(source) => {
+                    let interpreter = Interpreter::with_arguments(arguments[1..].to_vec());
+                    exit_code(interpreter.evaluate_reader(source).map(|_| ()))
+                }
+                Err(err) => cannot_open(file_name, err),
+            }
+        }
+        Some("check") if arguments.len() == 2 => {
+            let file_name = &arguments[1];
+            match open_source(file_name) {
+                Ok(source) => exit_code(Interpreter::new().check_reader(source)),
+                Err(err) => cannot_open(file_name, err),
+            }
+        }
+        Some("repl") if arguments.len() == 1 => repl::start(),
+        Some("eval") if arguments.len() == 3 && arguments[1] == "-e" => {
+            let source = Cursor::new(arguments[2].clone().into_bytes());
+            exit_code(Interpreter::new().evaluate_reader(source).map(|_| ()))
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn open_source(file_name: &str) -> std::io::Result<Box<dyn Read + Send>> {
+    if file_name == "-" {
+        Ok(Box::new(std::io::stdin()))
+    } else {
+        Ok(Box::new(std::fs::File::open(file_name)?))
+    }
+}
+
+fn cannot_open(file_name: &str, err: std::io::Error) -> ExitCode {
+    eprintln!("Unable to open file '{file_name}': {err}");
+    ExitCode::FAILURE
+}
 
-    thread::scope(move |scope| {
-        scope.spawn(move || evaluator.evaluate());
-        scope.spawn(move || parser.parse());
-        scope.spawn(move || lexer.lex());
-        file_reader.read();
-    });
+fn exit_code(result: Result<(), CompilerError>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -2,87 +2,212 @@ use super::block::Block;
 use super::seperated_statements::SeperatedStatements;
 use crate::common::literal::Literal;
 use crate::common::operators::Operator;
+use crate::common::span::Span;
 use std::fmt::Display;
 use std::sync::{Arc, RwLock};
 
+// `key: value` inside a map literal
+#[derive(Debug)]
+pub(crate) struct KeyValuePair {
+    pub(crate) key: String,
+    pub(crate) value: AbstractSyntaxTree,
+}
+
+// every node ends with the span of the source it was parsed from
 #[derive(Debug)]
 pub(crate) enum AbstractSyntaxTree {
     // Factors
-    Literal(Literal),
-    Identifier(String),
-    // Object(SeperatedStatements<KeyValuePair>),
+    Literal(Literal, Span),
+    Identifier(String, Span),
+    InterpolatedString(Vec<AbstractSyntaxTree>, Span), // text literals and expressions, in order
+    ListExpression(SeperatedStatements<Box<AbstractSyntaxTree>>, Span),
+    MapExpression(SeperatedStatements<KeyValuePair>, Span),
     // Expressions
-    UnaryExpression(Operator, Box<AbstractSyntaxTree>),
+    UnaryExpression(Operator, Box<AbstractSyntaxTree>, Span),
     BinaryExpression(
         Box<AbstractSyntaxTree>, // left
         Operator,                // operator
         Box<AbstractSyntaxTree>, // right
+        Span,
+    ),
+    ParenthesizedExpression(Box<AbstractSyntaxTree>, Span),
+    IndexExpression(
+        Box<AbstractSyntaxTree>, // list, string or map
+        Box<AbstractSyntaxTree>, // index or key
+        Span,
+    ),
+    MemberExpression(
+        Box<AbstractSyntaxTree>, // map
+        String,                  // key
+        Span,
+    ),
+    RangeExpression(
+        Box<AbstractSyntaxTree>,         // start
+        Box<AbstractSyntaxTree>,         // end
+        bool,                            // whether the end is included
+        Option<Box<AbstractSyntaxTree>>, // step
+        Span,
     ),
-    ParenthesizedExpression(Box<AbstractSyntaxTree>),
     AssignmentExpression(
+        Box<AbstractSyntaxTree>, // identifier, index or member
+        Operator,                // assignment operator
+        Box<AbstractSyntaxTree>, // expression
+        Span,
+    ),
+    MutableAssignmentExpression(
         String,                  // identifier
         Operator,                // assignment operator
         Box<AbstractSyntaxTree>, // expression
+        Span,
     ),
 
     // statements
-    BlockStatement(Arc<RwLock<Block>>),
+    BlockStatement(Arc<RwLock<Block>>, Span),
     IfStatement(
         Box<AbstractSyntaxTree>,         // condition
         Box<AbstractSyntaxTree>,         // block
         Option<Box<AbstractSyntaxTree>>, // else statement
+        Span,
     ),
-    ElseStatement(Box<AbstractSyntaxTree>), // block or if statement
+    ElseStatement(Box<AbstractSyntaxTree>, Span), // block or if statement
 
     LoopStatement(
         Box<AbstractSyntaxTree>, // condition
         Box<AbstractSyntaxTree>, // block or statement
+        Span,
+    ),
+    ForStatement(
+        Vec<String>, // the names given to each item, or to its index or key and itself
+        Box<AbstractSyntaxTree>, // range, list, string or map
+        Box<AbstractSyntaxTree>, // block or statement
+        Span,
     ),
     CallStatement(
-        String,                                       // name
+        Box<AbstractSyntaxTree>,                      // callee
         SeperatedStatements<Box<AbstractSyntaxTree>>, // arguments
+        Span,
     ),
-    ReturnStatement(Box<AbstractSyntaxTree>),
-    BreakStatement(Box<AbstractSyntaxTree>),
-    SkipStatement(Box<AbstractSyntaxTree>),
+    ReturnStatement(Box<AbstractSyntaxTree>, Span),
+    BreakStatement(Box<AbstractSyntaxTree>, Span),
+    SkipStatement(Box<AbstractSyntaxTree>, Span),
 }
 
 impl AbstractSyntaxTree {
     pub(crate) fn to_block(&self) -> Result<Arc<RwLock<Block>>, String> {
-        if let AbstractSyntaxTree::BlockStatement(block) = self {
+        if let AbstractSyntaxTree::BlockStatement(block, _) = self {
             Ok(Arc::clone(block))
         } else {
             Err("Not a block statement".to_string())
         }
     }
+
+    // the variable that holds the list or map being assigned into
+    pub(crate) fn assigned_variable(&self) -> Option<&str> {
+        match self {
+            AbstractSyntaxTree::Identifier(name, _) => Some(name),
+            AbstractSyntaxTree::IndexExpression(container, _, _)
+            | AbstractSyntaxTree::MemberExpression(container, _, _) => {
+                container.assigned_variable()
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn span(&self) -> Span {
+        match self {
+            AbstractSyntaxTree::Literal(_, span)
+            | AbstractSyntaxTree::Identifier(_, span)
+            | AbstractSyntaxTree::InterpolatedString(_, span)
+            | AbstractSyntaxTree::ListExpression(_, span)
+            | AbstractSyntaxTree::IndexExpression(_, _, span)
+            | AbstractSyntaxTree::MapExpression(_, span)
+            | AbstractSyntaxTree::MemberExpression(_, _, span)
+            | AbstractSyntaxTree::RangeExpression(_, _, _, _, span)
+            | AbstractSyntaxTree::UnaryExpression(_, _, span)
+            | AbstractSyntaxTree::BinaryExpression(_, _, _, span)
+            | AbstractSyntaxTree::ParenthesizedExpression(_, span)
+            | AbstractSyntaxTree::AssignmentExpression(_, _, _, span)
+            | AbstractSyntaxTree::MutableAssignmentExpression(_, _, _, span)
+            | AbstractSyntaxTree::BlockStatement(_, span)
+            | AbstractSyntaxTree::IfStatement(_, _, _, span)
+            | AbstractSyntaxTree::ElseStatement(_, span)
+            | AbstractSyntaxTree::LoopStatement(_, _, span)
+            | AbstractSyntaxTree::ForStatement(_, _, _, span)
+            | AbstractSyntaxTree::CallStatement(_, _, span)
+            | AbstractSyntaxTree::ReturnStatement(_, span)
+            | AbstractSyntaxTree::BreakStatement(_, span)
+            | AbstractSyntaxTree::SkipStatement(_, span) => *span,
+        }
+    }
 }
 
 impl Display for AbstractSyntaxTree {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let output = match self {
-            AbstractSyntaxTree::UnaryExpression(operator, operand) => {
+            AbstractSyntaxTree::UnaryExpression(operator, operand, _) => {
                 format!("{}{}", operator, operand)
             }
-            AbstractSyntaxTree::BinaryExpression(left, operator, right) => {
+            AbstractSyntaxTree::BinaryExpression(left, operator, right, _) => {
                 format!("{} {} {}", left, operator, right)
             }
-            AbstractSyntaxTree::Literal(value) => format!("{}", value),
-            AbstractSyntaxTree::AssignmentExpression(identifier, equals, expression) => {
-                format!("{} {} {}", identifier, equals, expression)
+            AbstractSyntaxTree::Literal(value, _) => format!("{}", value),
+            AbstractSyntaxTree::AssignmentExpression(target, equals, expression, _) => {
+                format!("{} {} {}", target, equals, expression)
+            }
+            AbstractSyntaxTree::MutableAssignmentExpression(name, equals, expression, _) => {
+                format!("let {} {} {}", name, equals, expression)
             }
-            AbstractSyntaxTree::ParenthesizedExpression(expression) => {
+            AbstractSyntaxTree::ParenthesizedExpression(expression, _) => {
                 format!("( {} )", expression)
             }
-            AbstractSyntaxTree::Identifier(name) => name.to_string(),
+            AbstractSyntaxTree::Identifier(name, _) => name.to_string(),
+            AbstractSyntaxTree::ListExpression(items, _) => {
+                let items: Vec<String> = items.iter().map(ToString::to_string).collect();
+                format!("[{}]", items.join(", "))
+            }
+            AbstractSyntaxTree::IndexExpression(list, index, _) => format!("{list}[{index}]"),
+            AbstractSyntaxTree::MapExpression(entries, _) => {
+                let entries: Vec<String> = entries
+                    .iter()
+                    .map(|entry| format!("{}: {}", entry.key, entry.value))
+                    .collect();
+                format!("{{ {} }}", entries.join(", "))
+            }
+            AbstractSyntaxTree::MemberExpression(map, key, _) => format!("{map}.{key}"),
+            AbstractSyntaxTree::RangeExpression(start, end, inclusive, step, _) => {
+                let dots = if *inclusive { "..=" } else { ".." };
+                match step {
+                    Some(step) => format!("{start}{dots}{end} step {step}"),
+                    None => format!("{start}{dots}{end}"),
+                }
+            }
+            AbstractSyntaxTree::InterpolatedString(parts, _) => {
+                let mut text = "$\"".to_string();
+                for part in parts {
+                    match part {
+                        AbstractSyntaxTree::Literal(literal, _) => text
+                            .push_str(&literal.to_string().replace('{', "{{").replace('}', "}}")),
+                        expression => text.push_str(&format!("{{{expression}}}")),
+                    }
+                }
+                text.push('"');
+                text
+            }
 
-            AbstractSyntaxTree::BlockStatement(_) => "{{ block }}".to_string(),
-            AbstractSyntaxTree::IfStatement(_, _, _) => "if condition {{ block }}".to_string(),
-            AbstractSyntaxTree::ElseStatement(_) => "else {{ block }}".to_string(),
-            AbstractSyntaxTree::LoopStatement(_, _) => "loop until condition {{ }}".to_string(),
-            AbstractSyntaxTree::CallStatement(name, _) => format!("Function call: {name}"),
-            AbstractSyntaxTree::ReturnStatement(_) => "return".to_string(),
-            AbstractSyntaxTree::BreakStatement(_) => "break".to_string(),
-            AbstractSyntaxTree::SkipStatement(_) => "skip".to_string(),
+            AbstractSyntaxTree::BlockStatement(..) => "{{ block }}".to_string(),
+            AbstractSyntaxTree::IfStatement(..) => "if condition {{ block }}".to_string(),
+            AbstractSyntaxTree::ElseStatement(..) => "else {{ block }}".to_string(),
+            AbstractSyntaxTree::LoopStatement(..) => "loop until condition {{ }}".to_string(),
+            AbstractSyntaxTree::ForStatement(names, iterable, ..) => {
+                format!("loop for {} in {iterable} {{ }}", names.join(", "))
+            }
+            AbstractSyntaxTree::CallStatement(callee, arguments, _) => {
+                let arguments: Vec<String> = arguments.iter().map(ToString::to_string).collect();
+                format!("{callee}({})", arguments.join(", "))
+            }
+            AbstractSyntaxTree::ReturnStatement(..) => "return".to_string(),
+            AbstractSyntaxTree::BreakStatement(..) => "break".to_string(),
+            AbstractSyntaxTree::SkipStatement(..) => "skip".to_string(),
         };
         write!(f, "{output}")
     }
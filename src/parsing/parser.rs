@@ -11,31 +11,35 @@ use crate::common::operators::arithmetic::Arithmetic;
 use crate::common::operators::assignment::Assingment;
 use crate::common::operators::logical::Logical;
 use crate::common::operators::relational::Relational;
-use crate::common::operators::Operator;
 use crate::common::operators::Operator::*;
+use crate::common::operators::{Associativity, Operator};
+use crate::common::span::Span;
 use crate::lexing::keywords::Keyword;
-use crate::lexing::symbols::Symbol::*;
+use crate::lexing::symbols::Symbol::{self, *};
 use crate::lexing::token::{Token, TokenKind};
-use crate::parsing::ast::AbstractSyntaxTree;
+use crate::parsing::ast::{AbstractSyntaxTree, KeyValuePair};
 
 pub(crate) struct Parser {
     token_receiver: Receiver<Token>,
     statement_transmitter: Sender<AbstractSyntaxTree>,
-    global_block: Arc<RwLock<Block>>,
     temp_token_buf: Vec<Token>,
+    // every syntax error found so far, parsing goes on after them
+    errors: Vec<CompilerError>,
+    // set by the first statement that failed, even when its error came from the lexer
+    failed: bool,
 }
 
 impl Parser {
     pub(crate) fn new(
         token_receiver: Receiver<Token>,
         statement_transmitter: Sender<AbstractSyntaxTree>,
-        global_block: Arc<RwLock<Block>>,
     ) -> Self {
         Self {
             token_receiver,
             statement_transmitter,
-            global_block,
             temp_token_buf: Vec::new(),
+            errors: Vec::new(),
+            failed: false,
         }
     }
 
@@ -45,336 +49,611 @@ impl Parser {
         } else {
             self.token_receiver
                 .recv()
-                .unwrap_or_else(|_| Token::new(TokenKind::EndOfFile, 0, 0))
+                .unwrap_or_else(|_| Token::new(TokenKind::EndOfFile, Span::default()))
         }
     }
 
-    pub(crate) fn parse(mut self) {
+    // a statement that fails to parse is skipped and parsing goes on with the next one,
+    // so that every syntax error of the source is returned at once.
+    // the statements are only sent on when the whole source parsed, none of a
+    // source with errors is evaluated
+    pub(crate) fn parse(mut self) -> Result<(), CompilerError> {
+        let mut statements = Vec::new();
         let mut current = self.get_current_token();
         while TokenKind::EndOfFile != current.kind {
-            self.temp_token_buf.push(current);
-            let statement = match self.parse_statement(Arc::clone(&self.global_block)) {
-                Ok(statement) => statement,
-                Err(err) => {
-                    eprintln!("{err}");
-                    panic!("{err}")
+            if !is_terminator(&current.kind) {
+                let start = current.span;
+                self.temp_token_buf.push(current);
+                match self.parse_terminated_statement() {
+                    Ok(statement) if !self.failed => statements.push(statement),
+                    Ok(_) => {}
+                    Err(err) => self.recover(err, start, false),
                 }
-            };
-            self.statement_transmitter.send(statement).unwrap();
+            }
             current = self.get_current_token();
         }
+        if !self.failed {
+            for statement in statements {
+                if self.statement_transmitter.send(statement).is_err() {
+                    // evaluator stopped listening
+                    break;
+                }
+            }
+        }
+        CompilerError::from_errors(self.errors)
     }
 
-    fn parse_statement(
-        &mut self,
-        block: Arc<RwLock<Block>>,
-    ) -> Result<AbstractSyntaxTree, CompilerError> {
+    fn parse_terminated_statement(&mut self) -> Result<AbstractSyntaxTree, CompilerError> {
+        let statement = self.parse_statement()?;
+        self.match_end_of_statement()?;
+        Ok(statement)
+    }
+
+    // remembers the error of the statement that started at `start` and skips the rest of it.
+    // outside of a block, a `}` is skipped like any other token
+    fn recover(&mut self, err: CompilerError, start: Span, in_block: bool) {
+        self.failed = true;
+        // the token the error is about was already taken, the braces it opens
+        // or closes still count
+        let mut depth = 0;
+        if let Some((kind, span)) = offending_token(&err) {
+            match kind {
+                TokenKind::Symbol(OpenCurlyBracket) => depth = 1,
+                // unless the statement starts with it, there is no block for it to close
+                TokenKind::Symbol(CloseCurlyBracket) if in_block && span != start => self
+                    .temp_token_buf
+                    .push(Token::new(TokenKind::Symbol(CloseCurlyBracket), span)),
+                TokenKind::EndOfFile => self
+                    .temp_token_buf
+                    .push(Token::new(TokenKind::EndOfFile, span)),
+                _ => {}
+            }
+        }
+        // the lexer already reported what is wrong with its error tokens
+        if !is_about_error_token(&err) {
+            self.errors.push(err.at(start));
+        }
+        self.synchronize(depth, in_block);
+    }
+
+    // skips tokens up to the start of the next statement: after a new line or `;`,
+    // before the `}` that closes the block, or before a keyword that starts a statement
+    fn synchronize(&mut self, mut depth: usize, in_block: bool) {
+        loop {
+            let token = self.get_current_token();
+            match &token.kind {
+                kind if is_terminator(kind) && depth == 0 => return,
+                TokenKind::Symbol(OpenCurlyBracket) => depth += 1,
+                TokenKind::Symbol(CloseCurlyBracket) if depth > 0 => depth -= 1,
+                TokenKind::Symbol(CloseCurlyBracket) if in_block => {
+                    self.temp_token_buf.push(token);
+                    return;
+                }
+                TokenKind::EndOfFile
+                | TokenKind::Keyword(
+                    Keyword::If | Keyword::Loop | Keyword::Return | Keyword::Break | Keyword::Skip,
+                ) if depth == 0 => {
+                    self.temp_token_buf.push(token);
+                    return;
+                }
+                TokenKind::EndOfFile => {
+                    self.temp_token_buf.push(token);
+                    return;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // a statement ends with `;`, a new line, the `}` of its block or the end of the file
+    fn match_end_of_statement(&mut self) -> Result<(), CompilerError> {
         let token = self.get_current_token();
-        match &token.kind {
-            TokenKind::Symbol(OpenParanthesis) => {
+        match token.kind {
+            kind if is_terminator(&kind) => Ok(()),
+            TokenKind::Symbol(CloseCurlyBracket) | TokenKind::EndOfFile => {
                 self.temp_token_buf.push(token);
-                self.parse_function_statement(block)
+                Ok(())
             }
+            kind => Err(CompilerError::ExpectedEndOfStatement(kind, token.span)),
+        }
+    }
+
+    // whether the statement being parsed ends before the next token, without taking it
+    fn is_end_of_statement(&mut self) -> bool {
+        let token = self.get_current_token();
+        let is_end = is_terminator(&token.kind)
+            || matches!(
+                token.kind,
+                TokenKind::Symbol(CloseCurlyBracket)
+                    | TokenKind::Keyword(Keyword::Else)
+                    | TokenKind::EndOfFile
+            );
+        self.temp_token_buf.push(token);
+        is_end
+    }
+
+    // skips the new lines between the header of a statement and its body,
+    // returns the last one so that it can be put back
+    fn skip_new_lines(&mut self) -> Option<Token> {
+        let mut new_line = None;
+        loop {
+            let token = self.get_current_token();
+            if TokenKind::NewLine != token.kind {
+                self.temp_token_buf.push(token);
+                return new_line;
+            }
+            new_line = Some(token);
+        }
+    }
+
+    fn parse_statement(&mut self) -> Result<AbstractSyntaxTree, CompilerError> {
+        let token = self.get_current_token();
+        let span = token.span;
+        match &token.kind {
+            // `{ key: value }` at the start of a statement is a map, every other `{` a block
             TokenKind::Symbol(OpenCurlyBracket) => {
-                Ok(AbstractSyntaxTree::BlockStatement(self.parse_block(block)?))
+                self.temp_token_buf.push(token);
+                if self.is_map_start(false) {
+                    self.parse_expression()
+                } else {
+                    self.get_current_token();
+                    self.parse_block(span)
+                }
             }
-            TokenKind::Keyword(Keyword::Loop) => self.parse_loop_statement(block),
-            TokenKind::Keyword(Keyword::Return) => self.parse_return_statement(block),
-            TokenKind::Keyword(Keyword::Break) => self.parse_break_statement(block),
-            TokenKind::Keyword(Keyword::Skip) => self.parse_skip_statement(block),
+            TokenKind::Keyword(Keyword::Loop) => self.parse_loop_statement(span),
+            TokenKind::Keyword(Keyword::Return) => self.parse_return_statement(span),
+            TokenKind::Keyword(Keyword::Break) => self.parse_break_statement(span),
+            TokenKind::Keyword(Keyword::Skip) => self.parse_skip_statement(span),
             _ => {
                 self.temp_token_buf.push(token);
-                self.parse_expression(block)
+                self.parse_expression()
             }
         }
     }
 
+    // a statement whose value is used, where `{}` is an empty map rather than an empty block
+    fn parse_value(&mut self) -> Result<AbstractSyntaxTree, CompilerError> {
+        if self.is_map_start(true) {
+            self.parse_expression()
+        } else {
+            self.parse_statement()
+        }
+    }
+
+    // looks past the next `{` for a `key:`, an empty `{}` counts as a map when `empty_is_map`
+    fn is_map_start(&mut self, empty_is_map: bool) -> bool {
+        let open = self.get_current_token();
+        let mut is_map = false;
+        let mut tokens = vec![];
+        if TokenKind::Symbol(OpenCurlyBracket) == open.kind {
+            let mut next = self.get_current_token();
+            while TokenKind::NewLine == next.kind {
+                tokens.push(next);
+                next = self.get_current_token();
+            }
+            let is_key = matches!(next.kind, TokenKind::Identifier(_) | TokenKind::Literal(_));
+            let is_close = TokenKind::Symbol(CloseCurlyBracket) == next.kind;
+            tokens.push(next);
+            if is_key {
+                let colon = self.get_current_token();
+                is_map = TokenKind::Symbol(Colon) == colon.kind;
+                tokens.push(colon);
+            } else {
+                is_map = is_close && empty_is_map;
+            }
+        }
+        for token in tokens.into_iter().rev() {
+            self.temp_token_buf.push(token);
+        }
+        self.temp_token_buf.push(open);
+        is_map
+    }
+
     fn parse_break_statement(
         &mut self,
-        parent: Arc<RwLock<Block>>,
+        keyword: Span,
     ) -> Result<AbstractSyntaxTree, CompilerError> {
-        let current = self.get_current_token();
-        if let TokenKind::Symbol(symbol) = current.kind {
-            if symbol == Semicolon {
-                return Ok(AbstractSyntaxTree::BreakStatement(Box::new(
-                    AbstractSyntaxTree::Literal(Literal::from(false)),
-                )));
-            }
+        if self.is_end_of_statement() {
+            return Ok(AbstractSyntaxTree::BreakStatement(
+                Box::new(AbstractSyntaxTree::Literal(Literal::from(false), keyword)),
+                keyword,
+            ));
         }
-        self.temp_token_buf.push(current);
-        let returnable = self.parse_statement(parent)?;
-        Ok(AbstractSyntaxTree::ReturnStatement(Box::new(returnable)))
+        let returnable = self.parse_statement()?;
+        let span = keyword.to(returnable.span());
+        Ok(AbstractSyntaxTree::BreakStatement(
+            Box::new(returnable),
+            span,
+        ))
     }
 
     fn parse_return_statement(
         &mut self,
-        parent: Arc<RwLock<Block>>,
+        keyword: Span,
     ) -> Result<AbstractSyntaxTree, CompilerError> {
-        let current = self.get_current_token();
-        if let TokenKind::Symbol(symbol) = current.kind {
-            if symbol == Semicolon {
-                return Ok(AbstractSyntaxTree::ReturnStatement(Box::new(
-                    AbstractSyntaxTree::Literal(Literal::from(false)),
-                )));
-            }
+        if self.is_end_of_statement() {
+            return Ok(AbstractSyntaxTree::ReturnStatement(
+                Box::new(AbstractSyntaxTree::Literal(Literal::from(false), keyword)),
+                keyword,
+            ));
         }
-        self.temp_token_buf.push(current);
-        let returnable = self.parse_statement(parent)?;
-        Ok(AbstractSyntaxTree::ReturnStatement(Box::new(returnable)))
+        let returnable = self.parse_value()?;
+        let span = keyword.to(returnable.span());
+        Ok(AbstractSyntaxTree::ReturnStatement(
+            Box::new(returnable),
+            span,
+        ))
     }
 
-    fn parse_skip_statement(
-        &mut self,
-        parent: Arc<RwLock<Block>>,
-    ) -> Result<AbstractSyntaxTree, CompilerError> {
-        let current = self.get_current_token();
-        if let TokenKind::Symbol(symbol) = current.kind {
-            if symbol == Semicolon {
-                return Ok(AbstractSyntaxTree::SkipStatement(Box::new(
-                    AbstractSyntaxTree::Literal(Literal::from(1)),
-                )));
-            }
+    fn parse_skip_statement(&mut self, keyword: Span) -> Result<AbstractSyntaxTree, CompilerError> {
+        if self.is_end_of_statement() {
+            return Ok(AbstractSyntaxTree::SkipStatement(
+                Box::new(AbstractSyntaxTree::Literal(Literal::from(1), keyword)),
+                keyword,
+            ));
         }
-        self.temp_token_buf.push(current);
-        let skip_count = self.parse_expression(parent)?;
-        Ok(AbstractSyntaxTree::SkipStatement(Box::new(skip_count)))
+        let skip_count = self.parse_expression()?;
+        let span = keyword.to(skip_count.span());
+        Ok(AbstractSyntaxTree::SkipStatement(
+            Box::new(skip_count),
+            span,
+        ))
     }
 
-    fn parse_block(
-        &mut self,
-        parent: Arc<RwLock<Block>>,
-    ) -> Result<Arc<RwLock<Block>>, CompilerError> {
-        let block = Arc::new(RwLock::new(Block::from(parent)));
+    fn parse_block(&mut self, open: Span) -> Result<AbstractSyntaxTree, CompilerError> {
+        let block = Arc::new(RwLock::new(Block::new()));
         let mut current = self.get_current_token();
         while TokenKind::Symbol(CloseCurlyBracket) != current.kind
             && TokenKind::EndOfFile != current.kind
         {
-            self.temp_token_buf.push(current);
-            let statement = self.parse_statement(Arc::clone(&block))?;
-            block.write().unwrap().statements.push(statement);
+            if !is_terminator(&current.kind) {
+                let start = current.span;
+                self.temp_token_buf.push(current);
+                match self.parse_terminated_statement() {
+                    Ok(statement) => block.write().unwrap().statements.push(statement),
+                    Err(err) => self.recover(err, start, true),
+                }
+            }
             current = self.get_current_token();
         }
         if TokenKind::Symbol(CloseCurlyBracket) != current.kind {
             return Err(CompilerError::UnexpectedTokenWithExpected(
                 current.kind,
                 TokenKind::Symbol(CloseCurlyBracket),
-                current.line,
-                current.column,
+                current.span,
             ));
         }
-        Ok(block)
+        Ok(AbstractSyntaxTree::BlockStatement(
+            block,
+            open.to(current.span),
+        ))
     }
 
-    fn parse_if_statement(
-        &mut self,
-        block: Arc<RwLock<Block>>,
-    ) -> Result<AbstractSyntaxTree, CompilerError> {
-        let condition = self.parse_expression(Arc::clone(&block))?;
-        let if_block = self.parse_statement(Arc::clone(&block))?;
+    fn parse_if_statement(&mut self, keyword: Span) -> Result<AbstractSyntaxTree, CompilerError> {
+        let condition = self.parse_expression()?;
+        self.skip_new_lines();
+        let if_block = self.parse_statement()?;
+        let mut span = keyword.to(if_block.span());
+        // `else` may start on the line after the if block
+        let new_line = self.skip_new_lines();
         let current = self.get_current_token();
         let else_block = if TokenKind::Keyword(Keyword::Else) == current.kind {
-            Some(Box::new(self.parse_else_block(Arc::clone(&block))?))
+            let else_block = self.parse_else_block(current.span)?;
+            span = span.to(else_block.span());
+            Some(Box::new(else_block))
         } else {
             self.temp_token_buf.push(current);
+            if let Some(new_line) = new_line {
+                self.temp_token_buf.push(new_line);
+            }
             None
         };
         Ok(AbstractSyntaxTree::IfStatement(
             Box::new(condition),
             Box::new(if_block),
             else_block,
+            span,
         ))
     }
 
-    fn parse_else_block(
-        &mut self,
-        block: Arc<RwLock<Block>>,
-    ) -> Result<AbstractSyntaxTree, CompilerError> {
-        Ok(AbstractSyntaxTree::ElseStatement(Box::new(
-            self.parse_statement(Arc::clone(&block))?,
-        )))
+    fn parse_else_block(&mut self, keyword: Span) -> Result<AbstractSyntaxTree, CompilerError> {
+        self.skip_new_lines();
+        let statement = self.parse_statement()?;
+        let span = keyword.to(statement.span());
+        Ok(AbstractSyntaxTree::ElseStatement(Box::new(statement), span))
     }
 
-    fn parse_loop_statement(
-        &mut self,
-        block: Arc<RwLock<Block>>,
-    ) -> Result<AbstractSyntaxTree, CompilerError> {
-        let mut condition = AbstractSyntaxTree::Literal(Literal::from(true));
+    fn parse_loop_statement(&mut self, keyword: Span) -> Result<AbstractSyntaxTree, CompilerError> {
+        let mut condition = AbstractSyntaxTree::Literal(Literal::from(true), keyword);
         let current = self.get_current_token();
 
-        if TokenKind::Keyword(Keyword::While) == current.kind {
-            condition = self.parse_expression(Arc::clone(&block))?;
+        if self.is_for_start(&current) {
+            return self.parse_for_statement(keyword);
+        } else if TokenKind::Keyword(Keyword::While) == current.kind {
+            condition = self.parse_expression()?;
         } else if TokenKind::Keyword(Keyword::Until) == current.kind {
-            condition = self.parse_expression(Arc::clone(&block))?;
+            condition = self.parse_expression()?;
+            let span = condition.span();
             condition = AbstractSyntaxTree::UnaryExpression(
                 Operator::Logical(Logical::Not),
                 Box::new(condition),
+                span,
             );
         } else {
             self.temp_token_buf.push(current);
         }
 
-        let previous_state = block.read().unwrap().is_loop;
-        block.write().unwrap().is_loop = true;
-        let block_to_execute = self.parse_statement(Arc::clone(&block))?;
-        block.write().unwrap().is_loop = previous_state;
+        self.skip_new_lines();
+        let block_to_execute = self.parse_statement()?;
 
+        let span = keyword.to(block_to_execute.span());
         Ok(AbstractSyntaxTree::LoopStatement(
             Box::new(condition),
             Box::new(block_to_execute),
+            span,
         ))
     }
 
-    fn parse_function_statement(
-        &mut self,
-        block: Arc<RwLock<Block>>,
-    ) -> Result<AbstractSyntaxTree, CompilerError> {
-        let open_paranthesis = self.get_current_token();
-        let mut parameters: Vec<AbstractSyntaxTree> = Vec::new();
-        let mut current = self.get_current_token();
-        while TokenKind::Symbol(CloseParanthesis) != current.kind
-            && TokenKind::EndOfFile != current.kind
-        {
-            if let TokenKind::Identifier(name) = &current.kind {
-                let parameter = AbstractSyntaxTree::Identifier(name.to_owned());
-                parameters.push(parameter);
-            } else {
+    // `for` and `in` are only keywords in a for loop, so they can still name variables.
+    // `loop for` followed by a name starts one, `loop for(x)` calls a function named `for`
+    fn is_for_start(&mut self, current: &Token) -> bool {
+        if TokenKind::Identifier("for".to_string()) != current.kind {
+            return false;
+        }
+        let next = self.get_current_token();
+        let is_name = matches!(next.kind, TokenKind::Identifier(_));
+        self.temp_token_buf.push(next);
+        is_name
+    }
+
+    // `loop for item in items`, or `loop for index, item in items`
+    fn parse_for_statement(&mut self, keyword: Span) -> Result<AbstractSyntaxTree, CompilerError> {
+        let mut names = Vec::new();
+        loop {
+            let token = self.get_current_token();
+            let TokenKind::Identifier(name) = token.kind else {
+                return Err(CompilerError::UnexpectedToken(token.kind, token.span));
+            };
+            names.push(name);
+            let current = self.get_current_token();
+            if names.len() == 2 || TokenKind::Symbol(Comma) != current.kind {
                 self.temp_token_buf.push(current);
-                self.temp_token_buf.push(open_paranthesis);
-                return self.parse_expression(block);
+                break;
             }
-            let prev = current;
-            current = self.get_current_token();
-            if TokenKind::Symbol(CloseParanthesis) != current.kind
-                && TokenKind::Symbol(Comma) != current.kind
-            {
-                self.temp_token_buf.push(current);
-                self.temp_token_buf.push(prev);
-                self.temp_token_buf.push(open_paranthesis);
-                return self.parse_expression(block);
-            } else if TokenKind::Symbol(Comma) == current.kind {
-                current = self.get_current_token();
+        }
+        self.match_token(TokenKind::Identifier("in".to_string()))?;
+        let iterable = self.parse_expression()?;
+
+        self.skip_new_lines();
+        let block_to_execute = self.parse_statement()?;
+
+        let span = keyword.to(block_to_execute.span());
+        Ok(AbstractSyntaxTree::ForStatement(
+            names,
+            Box::new(iterable),
+            Box::new(block_to_execute),
+            span,
+        ))
+    }
+
+    // `(a, b) =>` starts a function, any other `(` a parenthesized expression
+    fn is_function_start(&mut self) -> bool {
+        let mut tokens = vec![self.get_current_token()];
+        let mut is_function = false;
+        if TokenKind::Symbol(OpenParanthesis) == tokens[0].kind {
+            let mut expects_name = true;
+            loop {
+                let token = self.get_current_token();
+                let kind = &token.kind;
+                let is_name = matches!(kind, TokenKind::Identifier(_));
+                let is_comma = TokenKind::Symbol(Comma) == *kind;
+                let is_close = TokenKind::Symbol(CloseParanthesis) == *kind;
+                tokens.push(token);
+                if is_close {
+                    let arrow = self.get_current_token();
+                    is_function = TokenKind::Symbol(FatArrow) == arrow.kind;
+                    tokens.push(arrow);
+                    break;
+                }
+                if (expects_name && !is_name) || (!expects_name && !is_comma) {
+                    break;
+                }
+                expects_name = !expects_name;
             }
         }
+        for token in tokens.into_iter().rev() {
+            self.temp_token_buf.push(token);
+        }
+        is_function
+    }
 
-        let equal = self.get_current_token();
-        let arrow = self.get_current_token();
+    // a function literal, after `is_function_start` found it
+    fn parse_function_literal(&mut self) -> Result<AbstractSyntaxTree, CompilerError> {
+        let open_paranthesis = self.get_current_token();
+        let mut parameters: Vec<AbstractSyntaxTree> = Vec::new();
+        loop {
+            let current = self.get_current_token();
+            match current.kind {
+                TokenKind::Identifier(name) => {
+                    parameters.push(AbstractSyntaxTree::Identifier(name, current.span))
+                }
+                TokenKind::Symbol(Comma) => {}
+                _ => break,
+            }
+        }
 
-        if TokenKind::Symbol(Equals) != equal.kind && TokenKind::Symbol(GreaterThan) != arrow.kind {
-            return Err(CompilerError::MissingArrow(
-                current.kind,
-                current.line,
-                current.column,
-            ));
+        let arrow = self.get_current_token();
+        if TokenKind::Symbol(FatArrow) != arrow.kind {
+            return Err(CompilerError::MissingArrow(arrow.kind, arrow.span));
         }
 
-        let previous_state = block.read().unwrap().is_function;
-        block.write().unwrap().is_function = true;
-        let function_block_ast = self.parse_statement(Arc::clone(&block))?;
-        block.write().unwrap().is_function = previous_state;
+        self.skip_new_lines();
+        let function_block_ast = self.parse_statement()?;
+        let span = open_paranthesis.span.to(function_block_ast.span());
         let function_block = if let Ok(block) = function_block_ast.to_block() {
             block
         } else {
-            let mut current_block = Block::from(vec![function_block_ast]);
-            current_block.parent = Some(block);
-            current_block.is_function = true;
-            Arc::new(RwLock::new(current_block))
+            Arc::new(RwLock::new(Block::from(vec![function_block_ast])))
         };
         let parameters = SeperatedStatements::new(Comma, OpenParanthesis, parameters);
         let function = Function::new(function_block, parameters);
         let function = DataType::Function(Arc::new(function));
         let function = Literal::from(function);
-        Ok(AbstractSyntaxTree::Literal(function))
+        Ok(AbstractSyntaxTree::Literal(function, span))
     }
 
+    // the callee can be any expression that gives a function
     fn parse_function_call_statement(
         &mut self,
-        name: String,
-        block: Arc<RwLock<Block>>,
+        callee: AbstractSyntaxTree,
     ) -> Result<AbstractSyntaxTree, CompilerError> {
-        let mut arguments: Vec<Box<AbstractSyntaxTree>> = Vec::new();
+        let (arguments, close) =
+            self.parse_seperated_statements(OpenParanthesis, CloseParanthesis)?;
+        let span = callee.span().to(close.span);
+        Ok(AbstractSyntaxTree::CallStatement(
+            Box::new(callee),
+            arguments,
+            span,
+        ))
+    }
+
+    // parses comma seperated expressions up to the closing symbol, after the opening one was taken
+    fn parse_seperated_statements(
+        &mut self,
+        open: Symbol,
+        close: Symbol,
+    ) -> Result<(SeperatedStatements<Box<AbstractSyntaxTree>>, Token), CompilerError> {
+        let mut statements: Vec<Box<AbstractSyntaxTree>> = Vec::new();
         let mut current = self.get_current_token();
-        while TokenKind::Symbol(CloseParanthesis) != current.kind
-            && TokenKind::EndOfFile != current.kind
-        {
+        while TokenKind::Symbol(close) != current.kind && TokenKind::EndOfFile != current.kind {
             self.temp_token_buf.push(current);
-            let expression = self.parse_expression(Arc::clone(&block))?;
-            arguments.push(Box::new(expression));
+            let expression = self.parse_expression()?;
+            statements.push(Box::new(expression));
             current = self.get_current_token();
-            if TokenKind::Symbol(CloseParanthesis) != current.kind
-                && TokenKind::Symbol(Comma) != current.kind
+            if TokenKind::Symbol(close) != current.kind && TokenKind::Symbol(Comma) != current.kind
             {
+                // at the end of the file, only the closing symbol is missing
+                let expected = if TokenKind::EndOfFile == current.kind {
+                    close
+                } else {
+                    Comma
+                };
                 return Err(CompilerError::UnexpectedTokenWithExpected(
                     current.kind,
-                    TokenKind::Symbol(Comma),
-                    current.line,
-                    current.column,
+                    TokenKind::Symbol(expected),
+                    current.span,
                 ));
+            } else if TokenKind::Symbol(Comma) == current.kind {
+                current = self.get_current_token();
             }
         }
         self.temp_token_buf.push(current);
-        self.match_token(TokenKind::Symbol(CloseParanthesis))?;
+        let close = self.match_token(TokenKind::Symbol(close))?;
+        Ok((SeperatedStatements::new(Comma, open, statements), close))
+    }
 
-        let arguments = SeperatedStatements::new(Comma, OpenParanthesis, arguments);
-        Ok(AbstractSyntaxTree::CallStatement(name, arguments))
+    fn parse_expression(&mut self) -> Result<AbstractSyntaxTree, CompilerError> {
+        self.parse_assignment_expression()
     }
 
-    fn parse_expression(
-        &mut self,
-        block: Arc<RwLock<Block>>,
-    ) -> Result<AbstractSyntaxTree, CompilerError> {
-        self.parse_assignment_expression(block)
+    // the target of an assignment is a variable, or an index or member of one
+    fn parse_assignment_expression(&mut self) -> Result<AbstractSyntaxTree, CompilerError> {
+        let token = self.get_current_token();
+        if TokenKind::Keyword(Keyword::Let) == token.kind {
+            return self.handle_mutable_keyword(token.span);
+        }
+        self.temp_token_buf.push(token);
+        let target = self.parse_range_expression()?;
+        let Some(operator) = self.match_assignment_operator() else {
+            return Ok(target);
+        };
+        if target.assigned_variable().is_none() {
+            return Err(CompilerError::InvalidAssignment);
+        }
+        let expression = self.parse_value()?;
+        let span = target.span().to(expression.span());
+        Ok(AbstractSyntaxTree::AssignmentExpression(
+            Box::new(target),
+            operator,
+            Box::new(expression),
+            span,
+        ))
     }
 
-    fn parse_assignment_expression(
-        &mut self,
-        block: Arc<RwLock<Block>>,
-    ) -> Result<AbstractSyntaxTree, CompilerError> {
-        let identifier_token = self.get_current_token();
-        match &identifier_token.kind {
-            TokenKind::Keyword(Keyword::Let) => return self.handle_mutable_keyword(block),
-            TokenKind::Identifier(name) => {
-                if let Some(operator) = self.match_assignment_operator() {
-                    let expression = self.parse_statement(block)?;
-
-                    return Ok(AbstractSyntaxTree::AssignmentExpression(
-                        name.to_owned(),
-                        operator,
-                        Box::new(expression),
-                    ));
-                }
+    // `start..end`, `start..=end` and either of them followed by `step n`
+    fn parse_range_expression(&mut self) -> Result<AbstractSyntaxTree, CompilerError> {
+        let start = self.parse_binary_expression(0)?;
+        let current = self.get_current_token();
+        let inclusive = match current.kind {
+            TokenKind::Symbol(DotDot) => false,
+            TokenKind::Symbol(DotDotEquals) => true,
+            _ => {
+                self.temp_token_buf.push(current);
+                return Ok(start);
             }
-            _ => {}
-        }
-        self.temp_token_buf.push(identifier_token);
-        self.parse_arithmetic_expression(0, block)
+        };
+        let end = self.parse_binary_expression(0)?;
+        let mut span = start.span().to(end.span());
+        let current = self.get_current_token();
+        // `step` is only a keyword here, elsewhere it can name a variable
+        let step = if TokenKind::Identifier("step".to_string()) == current.kind {
+            let step = self.parse_binary_expression(0)?;
+            span = span.to(step.span());
+            Some(Box::new(step))
+        } else {
+            self.temp_token_buf.push(current);
+            None
+        };
+        Ok(AbstractSyntaxTree::RangeExpression(
+            Box::new(start),
+            Box::new(end),
+            inclusive,
+            step,
+            span,
+        ))
     }
 
-    fn parse_arithmetic_expression(
+    // parses operators whose binding power is at least `minimum_power`,
+    // the powers and associativity of every operator are in `Operator::binary_binding_power`
+    fn parse_binary_expression(
         &mut self,
-        parent_precedence: u8,
-        block: Arc<RwLock<Block>>,
+        minimum_power: u8,
     ) -> Result<AbstractSyntaxTree, CompilerError> {
-        let mut left = if let Some(operator) = self.match_operator() {
-            if operator.get_unery_precedence() >= parent_precedence {
-                let expression = self.parse_arithmetic_expression(
-                    operator.get_unery_precedence(),
-                    Arc::clone(&block),
-                )?;
-                AbstractSyntaxTree::UnaryExpression(operator, Box::new(expression))
-            } else {
-                self.parse_factor(Arc::clone(&block))?
+        let prefix = self.peek_operator().and_then(|(operator, span)| {
+            let power = operator.prefix_binding_power()?;
+            Some((operator, span, power))
+        });
+        let mut left = match prefix {
+            Some((operator, operator_span, power)) => {
+                self.match_operator();
+                let expression = self.parse_binary_expression(power)?;
+                let span = operator_span.to(expression.span());
+                AbstractSyntaxTree::UnaryExpression(operator, Box::new(expression), span)
             }
-        } else {
-            self.parse_factor(Arc::clone(&block))?
+            None => self.parse_factor()?,
         };
 
-        while let Some(operator) = self.match_operator() {
-            let precedence = operator.get_binary_precedence();
-            if precedence <= parent_precedence {
+        while let Some((operator, _)) = self.peek_operator() {
+            let Some((power, associativity)) = operator.binary_binding_power() else {
+                break;
+            };
+            if power < minimum_power {
+                // left for the enclosing expression
                 break;
             }
-            let right = self.parse_arithmetic_expression(precedence, Arc::clone(&block))?;
-            left = AbstractSyntaxTree::BinaryExpression(Box::new(left), operator, Box::new(right));
+            self.match_operator();
+            let right_power = match associativity {
+                Associativity::Left => power + 1,
+                Associativity::Right => power,
+            };
+            let right = self.parse_binary_expression(right_power)?;
+            let span = left.span().to(right.span());
+            left = AbstractSyntaxTree::BinaryExpression(
+                Box::new(left),
+                operator,
+                Box::new(right),
+                span,
+            );
         }
         Ok(left)
     }
@@ -385,72 +664,162 @@ impl Parser {
             Ok(token)
         } else {
             Err(CompilerError::UnexpectedTokenWithExpected(
-                token.kind,
-                kind,
-                token.line,
-                token.column,
+                token.kind, kind, token.span,
             ))
         }
     }
 
-    fn parse_factor(
-        &mut self,
-        block: Arc<RwLock<Block>>,
-    ) -> Result<AbstractSyntaxTree, CompilerError> {
+    // a primary expression followed by any number of `[index]`, `.key` and `(arguments)`
+    fn parse_factor(&mut self) -> Result<AbstractSyntaxTree, CompilerError> {
+        let mut factor = self.parse_primary()?;
+        loop {
+            let current = self.get_current_token();
+            factor = match current.kind {
+                TokenKind::Symbol(OpenSquareBracket) => {
+                    let index = self.parse_expression()?;
+                    let close = self.match_token(TokenKind::Symbol(CloseSquareBracket))?;
+                    let span = factor.span().to(close.span);
+                    AbstractSyntaxTree::IndexExpression(Box::new(factor), Box::new(index), span)
+                }
+                TokenKind::Symbol(OpenParanthesis) => self.parse_function_call_statement(factor)?,
+                TokenKind::Symbol(Dot) => {
+                    let key = self.get_current_token();
+                    let TokenKind::Identifier(name) = key.kind else {
+                        return Err(CompilerError::UnexpectedToken(key.kind, key.span));
+                    };
+                    let span = factor.span().to(key.span);
+                    AbstractSyntaxTree::MemberExpression(Box::new(factor), name, span)
+                }
+                _ => {
+                    self.temp_token_buf.push(current);
+                    return Ok(factor);
+                }
+            };
+        }
+    }
+
+    // `{ key: value, "other key": value }`, after the `{` was taken.
+    // entries may go on over several lines
+    fn parse_map(&mut self, open: Span) -> Result<AbstractSyntaxTree, CompilerError> {
+        let mut entries = Vec::new();
+        loop {
+            self.skip_new_lines();
+            let key = self.get_current_token();
+            let name = match key.kind {
+                TokenKind::Symbol(CloseCurlyBracket) => {
+                    let entries = SeperatedStatements::new(Comma, OpenCurlyBracket, entries);
+                    return Ok(AbstractSyntaxTree::MapExpression(
+                        entries,
+                        open.to(key.span),
+                    ));
+                }
+                TokenKind::Identifier(name) => name,
+                TokenKind::Literal(Literal {
+                    value: DataType::String(name),
+                    ..
+                }) => name.to_string(),
+                kind => return Err(CompilerError::UnexpectedToken(kind, key.span)),
+            };
+            self.match_token(TokenKind::Symbol(Colon))?;
+            let value = self.parse_expression()?;
+            entries.push(KeyValuePair { key: name, value });
+
+            self.skip_new_lines();
+            let current = self.get_current_token();
+            match current.kind {
+                TokenKind::Symbol(Comma) => {}
+                TokenKind::Symbol(CloseCurlyBracket) => self.temp_token_buf.push(current),
+                kind => {
+                    return Err(CompilerError::UnexpectedTokenWithExpected(
+                        kind,
+                        TokenKind::Symbol(Comma),
+                        current.span,
+                    ))
+                }
+            }
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<AbstractSyntaxTree, CompilerError> {
+        if self.is_function_start() {
+            return self.parse_function_literal();
+        }
         let token = self.get_current_token();
+        let span = token.span;
         match token.kind {
-            TokenKind::Literal(variable) => Ok(AbstractSyntaxTree::Literal(variable.clone())),
+            TokenKind::Literal(variable) => Ok(AbstractSyntaxTree::Literal(variable.clone(), span)),
             TokenKind::Symbol(symbol) => match symbol {
                 OpenParanthesis => {
-                    let expression = self.parse_arithmetic_expression(0, block)?;
-                    self.match_token(TokenKind::Symbol(CloseParanthesis))?;
-                    Ok(AbstractSyntaxTree::ParenthesizedExpression(Box::new(
-                        expression,
-                    )))
+                    let expression = self.parse_range_expression()?;
+                    let close = self.match_token(TokenKind::Symbol(CloseParanthesis))?;
+                    Ok(AbstractSyntaxTree::ParenthesizedExpression(
+                        Box::new(expression),
+                        span.to(close.span),
+                    ))
+                }
+                OpenCurlyBracket => self.parse_map(span),
+                OpenSquareBracket => {
+                    let (items, close) =
+                        self.parse_seperated_statements(OpenSquareBracket, CloseSquareBracket)?;
+                    Ok(AbstractSyntaxTree::ListExpression(
+                        items,
+                        span.to(close.span),
+                    ))
                 }
                 symbol => Err(CompilerError::UnexpectedToken(
                     TokenKind::Symbol(symbol),
-                    token.line,
-                    token.column,
+                    span,
                 )),
             },
-            TokenKind::Identifier(name) => {
-                let current = self.get_current_token();
-                if TokenKind::Symbol(OpenParanthesis) == current.kind {
-                    self.parse_function_call_statement(name.to_string(), block)
-                } else {
-                    self.temp_token_buf.push(current);
-                    Ok(AbstractSyntaxTree::Identifier(name.clone()))
+            TokenKind::Identifier(name) => Ok(AbstractSyntaxTree::Identifier(name, span)),
+            TokenKind::Keyword(Keyword::If) => self.parse_if_statement(span),
+            TokenKind::InterpolationStart => self.parse_interpolated_string(span),
+            kind => Err(CompilerError::UnexpectedToken(kind, span)),
+        }
+    }
+
+    fn parse_interpolated_string(
+        &mut self,
+        start: Span,
+    ) -> Result<AbstractSyntaxTree, CompilerError> {
+        let mut parts = Vec::new();
+        loop {
+            let token = self.get_current_token();
+            match token.kind {
+                TokenKind::StringFragment(text) => {
+                    parts.push(AbstractSyntaxTree::Literal(Literal::from(text), token.span))
+                }
+                TokenKind::Symbol(OpenCurlyBracket) => {
+                    parts.push(self.parse_expression()?);
+                    self.match_token(TokenKind::Symbol(CloseCurlyBracket))?;
+                }
+                TokenKind::InterpolationEnd => {
+                    return Ok(AbstractSyntaxTree::InterpolatedString(
+                        parts,
+                        start.to(token.span),
+                    ))
                 }
+                kind => return Err(CompilerError::UnexpectedToken(kind, token.span)),
             }
-            TokenKind::Keyword(Keyword::If) => self.parse_if_statement(block),
-            kind => Err(CompilerError::UnexpectedToken(
-                kind,
-                token.line,
-                token.column,
-            )),
         }
     }
 
     fn handle_mutable_keyword(
         &mut self,
-        block: Arc<RwLock<Block>>,
+        keyword: Span,
     ) -> Result<AbstractSyntaxTree, CompilerError> {
         let current = self.get_current_token();
         if let TokenKind::Identifier(variable_name) = &current.kind {
-            if let Some(operator) = self.match_operator() {
+            if let Some((operator, _)) = self.match_operator() {
                 // `mutable` variable_name operator expression
-                let expression = self.parse_assignment_expression(Arc::clone(&block))?;
-                handle_mutable_assignment(variable_name, operator, expression, block)
+                let expression = self.parse_assignment_expression()?;
+                let span = keyword.to(expression.span());
+                handle_mutable_assignment(variable_name, operator, expression, span)
             } else {
                 // `mutable` variable_name
-                if block.read().unwrap().contains_symbol(variable_name) {
-                    Err(CompilerError::CannotConvertFromImmutableToMutable)
-                } else {
-                    Err(CompilerError::UnInitializedVariable(
-                        variable_name.to_string(),
-                    ))
-                }
+                Err(CompilerError::UnInitializedVariable(
+                    variable_name.to_string(),
+                ))
             }
         } else {
             Err(CompilerError::InvalidUseOfMutableKeyword)
@@ -459,229 +828,77 @@ impl Parser {
 
     fn match_assignment_operator(&mut self) -> Option<Operator> {
         let token = self.get_current_token();
-        if let TokenKind::Symbol(operator_symbol) = &token.kind {
-            let second_token = self.get_current_token();
-            match operator_symbol {
-                Equals => {
-                    // =
-                    if let TokenKind::Symbol(second_symbol) = second_token.kind {
-                        if second_symbol == Equals {
-                            // ==
-                            self.temp_token_buf.push(second_token);
-                            self.temp_token_buf.push(token);
-                            return None;
-                        }
-                    }
-                    self.temp_token_buf.push(second_token);
-                    // =
-                    return Some(Assignment(Assingment::Simple));
-                }
-                Plus => {
-                    // +
-                    if let TokenKind::Symbol(second_symbol) = second_token.kind {
-                        if second_symbol == Equals {
-                            // +=
-                            return Some(Assignment(Assingment::Addition));
-                        }
-                    }
-                }
-                Minus => {
-                    // -
-                    if let TokenKind::Symbol(second_symbol) = second_token.kind {
-                        if second_symbol == Equals {
-                            // -=
-                            return Some(Assignment(Assingment::Subtraction));
-                        }
-                    }
-                }
-                Asterisk => {
-                    // *
-                    if let TokenKind::Symbol(second_symbol) = second_token.kind {
-                        if second_symbol == Equals {
-                            // *=
-                            return Some(Assignment(Assingment::Multiplication));
-                        } else if second_symbol == Asterisk {
-                            // **
-                            let current = self.get_current_token();
-                            if let TokenKind::Symbol(third_token) = current.kind {
-                                if third_token == Equals {
-                                    // **=
-                                    return Some(Assignment(Assingment::Exponentiation));
-                                }
-                            }
-                            self.temp_token_buf.push(current);
-                        }
-                    }
-                }
-                Slash => {
-                    // /
-                    if let TokenKind::Symbol(second_symbol) = second_token.kind {
-                        if second_symbol == Equals {
-                            // /=
-                            return Some(Assignment(Assingment::Division));
-                        }
-                    }
-                }
-                Percent => {
-                    // %
-                    if let TokenKind::Symbol(second_symbol) = second_token.kind {
-                        if second_symbol == Equals {
-                            // %=
-                            return Some(Assignment(Assingment::Modulo));
-                        }
-                    }
-                }
-                _ => {}
+        let operator = match token.kind {
+            TokenKind::Symbol(Equals) => Assingment::Simple,
+            TokenKind::Symbol(PlusEquals) => Assingment::Addition,
+            TokenKind::Symbol(MinusEquals) => Assingment::Subtraction,
+            TokenKind::Symbol(AsteriskEquals) => Assingment::Multiplication,
+            TokenKind::Symbol(SlashEquals) => Assingment::Division,
+            TokenKind::Symbol(PercentEquals) => Assingment::Modulo,
+            TokenKind::Symbol(StarStarEquals) => Assingment::Exponentiation,
+            _ => {
+                self.temp_token_buf.push(token);
+                return None;
             }
-            self.temp_token_buf.push(second_token);
+        };
+        Some(Assignment(operator))
+    }
+
+    // returns the operator along with the span of its tokens
+    fn match_operator(&mut self) -> Option<(Operator, Span)> {
+        let (operator, tokens) = self.read_operator()?;
+        let span = tokens[0].span.to(tokens[tokens.len() - 1].span);
+        Some((operator, span))
+    }
+
+    // looks at the next operator without taking it
+    fn peek_operator(&mut self) -> Option<(Operator, Span)> {
+        let (operator, tokens) = self.read_operator()?;
+        let span = tokens[0].span.to(tokens[tokens.len() - 1].span);
+        for token in tokens.into_iter().rev() {
+            self.temp_token_buf.push(token);
         }
-        self.temp_token_buf.push(token);
-        None
+        Some((operator, span))
     }
 
-    fn match_operator(&mut self) -> Option<Operator> {
+    // returns the operator along with the tokens it was made of
+    fn read_operator(&mut self) -> Option<(Operator, Vec<Token>)> {
         let current = self.get_current_token();
         let operator = match &current.kind {
-            TokenKind::Symbol(operator_symbol) => {
-                let second_token = self.get_current_token();
-                match operator_symbol {
-                    Equals => {
-                        // =
-                        if let TokenKind::Symbol(second_symbol) = second_token.kind {
-                            if second_symbol == Equals {
-                                // ==
-                                return Some(Relational(Relational::Equality));
-                            }
-                        }
-                        self.temp_token_buf.push(second_token);
-                        // =
-                        Assignment(Assingment::Simple)
-                    }
-                    Plus => {
-                        // +
-                        if let TokenKind::Symbol(second_symbol) = second_token.kind {
-                            if second_symbol == Equals {
-                                // +=
-                                return Some(Assignment(Assingment::Addition));
-                            }
-                        }
-                        self.temp_token_buf.push(second_token);
-                        // +
-                        Arithmetic(Arithmetic::Addition)
-                    }
-                    Minus => {
-                        // -
-                        if let TokenKind::Symbol(second_symbol) = second_token.kind {
-                            if second_symbol == Equals {
-                                // -=
-                                return Some(Assignment(Assingment::Subtraction));
-                            }
-                        }
-                        self.temp_token_buf.push(second_token);
-                        // -
-                        Arithmetic(Arithmetic::Subtraction)
-                    }
-                    Asterisk => {
-                        // *
-                        if let TokenKind::Symbol(second_symbol) = second_token.kind {
-                            if second_symbol == Equals {
-                                // *=
-                                return Some(Assignment(Assingment::Multiplication));
-                            } else if second_symbol == Asterisk {
-                                // **
-                                let current = self.get_current_token();
-                                if let TokenKind::Symbol(third_token) = current.kind {
-                                    if third_token == Equals {
-                                        // **=
-                                        return Some(Assignment(Assingment::Exponentiation));
-                                    }
-                                }
-                                self.temp_token_buf.push(current);
-                                // **
-                                return Some(Arithmetic(Arithmetic::Exponentiation));
-                            }
-                        }
-                        self.temp_token_buf.push(second_token);
-                        // *
-                        Arithmetic(Arithmetic::Multiplication)
-                    }
-                    Slash => {
-                        // /
-                        if let TokenKind::Symbol(second_symbol) = second_token.kind {
-                            if second_symbol == Equals {
-                                // /=
-                                return Some(Assignment(Assingment::Division));
-                            }
-                        }
-                        self.temp_token_buf.push(second_token);
-                        // /
-                        Arithmetic(Arithmetic::Division)
-                    }
-                    Percent => {
-                        // %
-                        if let TokenKind::Symbol(second_symbol) = second_token.kind {
-                            if second_symbol == Equals {
-                                // %=
-                                return Some(Assignment(Assingment::Modulo));
-                            }
-                        }
-                        self.temp_token_buf.push(second_token);
-                        // %
-                        Arithmetic(Arithmetic::Modulo)
-                    }
-                    Exclamation => {
-                        // !
-                        if let TokenKind::Symbol(second_symbol) = second_token.kind {
-                            if second_symbol == Equals {
-                                // !=
-                                return Some(Relational(Relational::InEquality));
-                            }
-                        }
-                        self.temp_token_buf.push(second_token);
-                        // !
-                        Logical(Logical::Not)
-                    }
-                    GreaterThan => {
-                        // >
-                        if let TokenKind::Symbol(second_symbol) = second_token.kind {
-                            if second_symbol == Equals {
-                                // >=
-                                return Some(Relational(Relational::GreaterThanOrEquals));
-                            }
-                        }
-                        self.temp_token_buf.push(second_token);
-                        // >
-                        Relational(Relational::GreaterThan)
-                    }
-                    LessThan => {
-                        // <
-                        if let TokenKind::Symbol(second_symbol) = second_token.kind {
-                            if second_symbol == Equals {
-                                // <=
-                                return Some(Relational(Relational::LessThanOrEquals));
-                            }
-                        }
-                        self.temp_token_buf.push(second_token);
-                        // <
-                        Relational(Relational::LessThan)
-                    }
-                    _ => {
-                        self.temp_token_buf.push(second_token);
-                        self.temp_token_buf.push(current);
-                        return None;
-                    }
+            TokenKind::Symbol(operator_symbol) => match operator_symbol {
+                Plus => Arithmetic(Arithmetic::Addition),
+                Minus => Arithmetic(Arithmetic::Subtraction),
+                Asterisk => Arithmetic(Arithmetic::Multiplication),
+                Slash => Arithmetic(Arithmetic::Division),
+                Percent => Arithmetic(Arithmetic::Modulo),
+                StarStar => Arithmetic(Arithmetic::Exponentiation),
+                Equals => Assignment(Assingment::Simple),
+                PlusEquals => Assignment(Assingment::Addition),
+                MinusEquals => Assignment(Assingment::Subtraction),
+                AsteriskEquals => Assignment(Assingment::Multiplication),
+                SlashEquals => Assignment(Assingment::Division),
+                PercentEquals => Assignment(Assingment::Modulo),
+                StarStarEquals => Assignment(Assingment::Exponentiation),
+                EqualsEquals => Relational(Relational::Equality),
+                ExclamationEquals => Relational(Relational::InEquality),
+                LessThan => Relational(Relational::LessThan),
+                LessThanEquals => Relational(Relational::LessThanOrEquals),
+                GreaterThan => Relational(Relational::GreaterThan),
+                GreaterThanEquals => Relational(Relational::GreaterThanOrEquals),
+                Exclamation => Logical(Logical::Not),
+                _ => {
+                    self.temp_token_buf.push(current);
+                    return None;
                 }
-            }
+            },
             TokenKind::Keyword(keyword) => match keyword {
                 Keyword::Is => {
-                    let current = self.get_current_token();
-                    if let TokenKind::Keyword(keyword) = &current.kind {
-                        if &Keyword::Not == keyword {
-                            return Some(Relational(Relational::Equality));
-                        }
+                    let next = self.get_current_token();
+                    if TokenKind::Keyword(Keyword::Not) == next.kind {
+                        return Some((Relational(Relational::NonIdentity), vec![current, next]));
                     }
-                    self.temp_token_buf.push(current);
-                    Relational(Relational::Equality)
+                    self.temp_token_buf.push(next);
+                    Relational(Relational::Identity)
                 }
                 Keyword::And => Logical(Logical::And),
                 Keyword::Or => Logical(Logical::Or),
@@ -697,76 +914,42 @@ impl Parser {
                 return None;
             }
         };
-        Some(operator)
+        Some((operator, vec![current]))
     }
 }
 
+// whether the variable may be made mutable is checked by the resolver
 fn handle_mutable_assignment(
     variable_name: &str,
     operator: Operator,
     expression: AbstractSyntaxTree,
-    block: Arc<RwLock<Block>>,
+    span: Span,
 ) -> Result<AbstractSyntaxTree, CompilerError> {
-    let block = block.read().unwrap();
     match operator {
-        Assignment(Assingment::Simple) => {
-            if block.contains_symbol(variable_name) {
-                let old_variable = block.get_symbol(variable_name).unwrap();
-                if old_variable.is_mutable() {
-                    // `mutable` variable_name = old_expression
-                    // `mutable` variable_name = new_expression
-                    block.add_symbol(
-                        variable_name.to_string(),
-                        Literal::new_mutable(DataType::InternalUndefined),
-                    );
-                } else {
-                    // variable_name = old_expression
-                    // `mutable` variable_name = new_expression
-                    return Err(CompilerError::CannotConvertFromImmutableToMutable);
-                }
-            } else {
-                // `mutable` variable_name = expression
-                block.add_symbol(
-                    variable_name.to_string(),
-                    Literal::new_mutable(DataType::InternalUndefined),
-                );
-            }
-
-            Ok(AbstractSyntaxTree::AssignmentExpression(
-                variable_name.to_string(),
-                operator,
-                Box::new(expression),
-            ))
-        }
-        // mutable a += 10
-        Assignment(_) => {
-            if block.contains_symbol(variable_name) {
-                let old_variable = block.get_symbol(variable_name).unwrap();
-                if old_variable.is_mutable() {
-                    // `mutable` variable_name operator old_expression
-                    // `mutable` variable_name assignment_operator new expression
-                    block.add_symbol(
-                        variable_name.to_string(),
-                        Literal::new_mutable(DataType::InternalUndefined),
-                    );
-                } else {
-                    // variable_name operator expression
-                    // `mutable` variable_name assignment_operator new expression
-                    return Err(CompilerError::CannotConvertFromImmutableToMutable);
-                }
-                block.add_symbol(
-                    variable_name.to_string(),
-                    Literal::new_mutable(block.get_symbol(variable_name).unwrap().value),
-                );
-            } else {
-                return Err(CompilerError::UndefinedVariable(variable_name.to_string()));
-            }
-            Ok(AbstractSyntaxTree::AssignmentExpression(
-                variable_name.to_string(),
-                operator,
-                Box::new(expression),
-            ))
-        }
+        Assignment(_) => Ok(AbstractSyntaxTree::MutableAssignmentExpression(
+            variable_name.to_string(),
+            operator,
+            Box::new(expression),
+            span,
+        )),
         _ => Err(CompilerError::InvalidOperationAsAssignmentOperation),
     }
 }
+
+fn is_terminator(kind: &TokenKind) -> bool {
+    matches!(kind, TokenKind::Symbol(Semicolon) | TokenKind::NewLine)
+}
+
+fn offending_token(err: &CompilerError) -> Option<(&TokenKind, Span)> {
+    match err {
+        CompilerError::UnexpectedToken(kind, span)
+        | CompilerError::UnexpectedTokenWithExpected(kind, _, span)
+        | CompilerError::MissingArrow(kind, span)
+        | CompilerError::ExpectedEndOfStatement(kind, span) => Some((kind, *span)),
+        _ => None,
+    }
+}
+
+fn is_about_error_token(err: &CompilerError) -> bool {
+    matches!(offending_token(err), Some((TokenKind::Error, _)))
+}
@@ -26,7 +26,7 @@ impl<Item> SeperatedStatements<Item> {
         self.statements.len()
     }
 
-    pub(crate) fn iter(&self) -> Iter<Item> {
+    pub(crate) fn iter(&self) -> Iter<'_, Item> {
         self.statements.iter()
     }
 }
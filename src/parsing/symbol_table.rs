@@ -25,6 +25,14 @@ impl SymbolTable {
         self.table.get(name).cloned()
     }
 
+    pub(crate) fn get_mut(&mut self, name: &str) -> Option<&mut Literal> {
+        self.table.get_mut(name)
+    }
+
+    pub(crate) fn values(&self) -> impl Iterator<Item = &Literal> {
+        self.table.values()
+    }
+
     pub(crate) fn clear(&mut self) {
         self.table.clear();
     }
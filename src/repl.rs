@@ -0,0 +1,244 @@
+use std::io::{stdin, stdout, Cursor, Write};
+use std::iter::Peekable;
+use std::panic::{self, AssertUnwindSafe};
+use std::process::ExitCode;
+use std::str::Chars;
+use std::sync::mpsc;
+use std::thread;
+
+use unicode_ident::is_xid_continue;
+
+use crate::interpreter::Interpreter;
+use crate::lexing::token::TokenKind;
+use crate::lexing::{FileReader, Lexer};
+use crate::parsing::Parser;
+
+const HELP: &str = "commands:
+    :env            show the variables of the session
+    :ast <code>     show the syntax tree of the code
+    :tokens <code>  show the tokens of the code
+    :load <file>    run a file inside the session
+    :reset          forget every variable of the session
+    :help           show this message
+    :quit           leave the repl";
+
+/// Starts an interactive session on stdin and stdout.
+///
+/// One interpreter lives for the whole session, so every input sees
+/// the variables defined by the inputs before it.
+pub fn start() -> ExitCode {
+    let mut interpreter = Interpreter::new();
+    let mut buffer = String::new();
+    println!("prolang repl, type :help for the list of commands");
+    loop {
+        print!("{}", if buffer.is_empty() { ">> " } else { ".. " });
+        stdout().flush().unwrap();
+        let mut line = String::new();
+        match stdin().read_line(&mut line) {
+            Ok(0) | Err(_) => {
+                println!();
+                return ExitCode::SUCCESS;
+            }
+            Ok(_) => {}
+        }
+
+        if buffer.is_empty() {
+            if let Some(command) = line.trim().strip_prefix(':') {
+                if !run_command(command, &mut interpreter) {
+                    return ExitCode::SUCCESS;
+                }
+                continue;
+            }
+        }
+
+        buffer.push_str(&line);
+        if is_incomplete(&buffer) {
+            continue;
+        }
+        let source = std::mem::take(&mut buffer);
+        if source.trim().is_empty() {
+            continue;
+        }
+        // a crash while running one input should not end the session
+        match panic::catch_unwind(AssertUnwindSafe(|| interpreter.evaluate(&source))) {
+            Ok(Ok(value)) => println!("{value}"),
+            Ok(Err(err)) => eprintln!("{err}"),
+            Err(_) => {}
+        }
+    }
+}
+
+// returns false when the session should end
+fn run_command(command: &str, interpreter: &mut Interpreter) -> bool {
+    let (name, argument) = command
+        .split_once(char::is_whitespace)
+        .unwrap_or((command, ""));
+    let argument = argument.trim();
+    match name {
+        "env" => {
+            let environment = &interpreter.global().environment;
+            let symbols = environment.symbol_table().to_string();
+            println!("{}", symbols.trim_start());
+        }
+        "ast" => print_syntax_tree(argument),
+        "tokens" => print_tokens(argument),
+        "load" => match std::fs::File::open(argument) {
+            Ok(file) => {
+                if let Err(err) = interpreter.evaluate_reader(file) {
+                    eprintln!("{err}");
+                }
+            }
+            Err(err) => eprintln!("Unable to open file '{argument}': {err}"),
+        },
+        "reset" => interpreter.reset(),
+        "help" => println!("{HELP}"),
+        "quit" | "exit" => return false,
+        _ => eprintln!("Unknown command ':{name}', type :help for the list of commands"),
+    }
+    true
+}
+
+// input is incomplete while any bracket, string or block comment is left open,
+// brackets inside strings and comments are ignored
+fn is_incomplete(source: &str) -> bool {
+    let mut depth = 0;
+    let mut comment_depth = 0;
+    let mut chars = source.chars().peekable();
+    let mut previous = '\0';
+    while let Some(ch) = chars.next() {
+        // an `r` inside a word can not start a raw string
+        let starts_word = !is_xid_continue(previous);
+        previous = ch;
+        if comment_depth > 0 {
+            match (ch, chars.peek()) {
+                ('#', Some('[')) => {
+                    chars.next();
+                    comment_depth += 1;
+                }
+                (']', Some('#')) => {
+                    chars.next();
+                    comment_depth -= 1;
+                }
+                _ => {}
+            }
+            continue;
+        }
+        match ch {
+            '\'' | '"' => {
+                let mut closed = false;
+                while let Some(next) = chars.next() {
+                    if next == '\\' {
+                        chars.next();
+                    } else if next == ch {
+                        closed = true;
+                        break;
+                    }
+                }
+                // `"""` on its own opens a string that goes on over the next lines
+                if !closed {
+                    return true;
+                }
+            }
+            // `r"` and `r#"` start raw strings, where `\` is not an escape
+            'r' if starts_word => {
+                let mut lookahead = chars.clone();
+                let mut hashes = 0;
+                while lookahead.next_if_eq(&'#').is_some() {
+                    hashes += 1;
+                }
+                if lookahead.next_if_eq(&'"').is_some() {
+                    chars = lookahead;
+                    if !skip_raw_string(&mut chars, hashes) {
+                        return true;
+                    }
+                    previous = '"';
+                }
+            }
+            '#' if chars.peek() == Some(&'[') => {
+                chars.next();
+                comment_depth += 1;
+            }
+            '#' => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+            }
+            '(' | '{' | '[' => depth += 1,
+            ')' | '}' | ']' => depth -= 1,
+            _ => {}
+        }
+    }
+    depth > 0 || comment_depth > 0
+}
+
+// skips a raw string after its opening quote, it ends at a quote followed by `hashes` `#`s.
+// returns whether it was closed
+fn skip_raw_string(chars: &mut Peekable<Chars>, hashes: usize) -> bool {
+    while let Some(next) = chars.next() {
+        if next == '"' {
+            let mut closing = 0;
+            while closing < hashes && chars.next_if_eq(&'#').is_some() {
+                closing += 1;
+            }
+            if closing == hashes {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn print_tokens(source: &str) {
+    let (file_chunk_transmitter, file_chunk_receiver) = mpsc::channel();
+    let (token_transmitter, token_receiver) = mpsc::channel();
+
+    let source = Cursor::new(source.to_string().into_bytes());
+    let file_reader = FileReader::new(source, file_chunk_transmitter);
+    let lexer = Lexer::new(file_chunk_receiver, token_transmitter, 0);
+
+    thread::scope(move |scope| {
+        let lexing = scope.spawn(move || lexer.lex());
+        if let Err(err) = file_reader.read() {
+            eprintln!("{err}");
+        }
+        for token in token_receiver {
+            if TokenKind::EndOfFile == token.kind {
+                break;
+            }
+            println!("{}:{} {}", token.span.line, token.span.col, token);
+        }
+        if let Ok(Err(err)) = lexing.join() {
+            eprintln!("{err}");
+        }
+    });
+}
+
+fn print_syntax_tree(source: &str) {
+    let (file_chunk_transmitter, file_chunk_receiver) = mpsc::channel();
+    let (token_transmitter, token_receiver) = mpsc::channel();
+    let (statement_transmitter, statement_receiver) = mpsc::channel();
+
+    let source = Cursor::new(source.to_string().into_bytes());
+    let file_reader = FileReader::new(source, file_chunk_transmitter);
+    let lexer = Lexer::new(file_chunk_receiver, token_transmitter, 0);
+    let parser = Parser::new(token_receiver, statement_transmitter);
+
+    thread::scope(move |scope| {
+        let lexing = scope.spawn(move || lexer.lex());
+        let parsing = scope.spawn(move || parser.parse());
+        if let Err(err) = file_reader.read() {
+            eprintln!("{err}");
+        }
+        for statement in statement_receiver {
+            println!("{statement:#?}");
+        }
+        if let Ok(Err(err)) = lexing.join() {
+            eprintln!("{err}");
+        }
+        if let Ok(Err(err)) = parsing.join() {
+            eprintln!("{err}");
+        }
+    });
+}
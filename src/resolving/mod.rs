@@ -0,0 +1,4 @@
+pub(crate) mod resolver;
+pub(crate) mod scope;
+
+pub(crate) use resolver::Resolver;
@@ -0,0 +1,311 @@
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+
+use super::scope::{PendingFunction, Scope, ScopeKind, Variable};
+use crate::common::datatypes::DataType;
+use crate::common::errors::CompilerError;
+use crate::common::functions::Function;
+use crate::common::operators::assignment::Assingment;
+use crate::common::operators::Operator;
+use crate::evaluating::global::Global;
+use crate::parsing::ast::AbstractSyntaxTree;
+
+// checks the names, `return`s, `break`s and assignments of the whole program
+// before any of it runs
+pub(crate) struct Resolver {
+    statement_receiver: Receiver<AbstractSyntaxTree>,
+    global: Arc<Global>,
+    // from the global scope to the innermost one
+    scopes: Vec<Scope>,
+    // how many variables of each scope an assignment can see. inside a function
+    // resolved at the end of its scope, the variables declared after it are left out
+    limits: Vec<usize>,
+    errors: Vec<CompilerError>,
+}
+
+impl Resolver {
+    pub(crate) fn new(
+        statement_receiver: Receiver<AbstractSyntaxTree>,
+        global: Arc<Global>,
+    ) -> Self {
+        Self {
+            statement_receiver,
+            global,
+            scopes: Vec::new(),
+            limits: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    // returns the statements along with the global scope and the variables the program
+    // declares, to be kept for the next program of the session
+    pub(crate) fn resolve(mut self) -> Result<(Vec<AbstractSyntaxTree>, Scope), CompilerError> {
+        let statements: Vec<AbstractSyntaxTree> = self.statement_receiver.iter().collect();
+        let global_scope = self.global.scope.lock().unwrap().clone();
+        self.scopes.push(global_scope);
+        self.limits.push(usize::MAX);
+        for statement in statements.iter() {
+            self.resolve_statement(statement);
+        }
+        let global_scope = self.end_scope();
+        CompilerError::from_errors(self.errors).map(|_| (statements, global_scope))
+    }
+
+    fn begin_scope(&mut self, kind: ScopeKind) {
+        self.scopes.push(Scope::new(kind));
+        self.limits.push(usize::MAX);
+    }
+
+    // resolves the functions created in the scope, now that all of its variables are known
+    fn end_scope(&mut self) -> Scope {
+        let functions = std::mem::take(&mut self.scopes.last_mut().unwrap().functions);
+        for function in functions {
+            self.resolve_function(function);
+        }
+        self.limits.pop();
+        self.scopes.pop().unwrap()
+    }
+
+    fn resolve_function(&mut self, pending: PendingFunction) {
+        let limits = std::mem::replace(&mut self.limits, pending.limits);
+        self.begin_scope(ScopeKind::Function);
+        for parameter in pending.function.parameters.iter() {
+            if let AbstractSyntaxTree::Identifier(name, _) = parameter {
+                self.declare(name, false);
+            }
+        }
+        for statement in pending.function.block.read().unwrap().statements.iter() {
+            self.resolve_statement(statement);
+        }
+        self.end_scope();
+        self.limits = limits;
+    }
+
+    fn defer_function(&mut self, function: &Arc<Function>) {
+        let limits = self
+            .scopes
+            .iter()
+            .zip(self.limits.iter())
+            .map(|(scope, limit)| scope.len().min(*limit))
+            .collect();
+        let pending = PendingFunction {
+            function: Arc::clone(function),
+            limits,
+        };
+        self.scopes.last_mut().unwrap().functions.push(pending);
+    }
+
+    fn declare(&mut self, name: &str, mutable: bool) {
+        self.scopes.last_mut().unwrap().declare(name, mutable);
+    }
+
+    // any variable that may exist when the code runs, even one declared after it
+    fn is_declared(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.get(name).is_some())
+    }
+
+    // the variable an assignment to `name` changes
+    fn find_assignable(&self, name: &str) -> Option<Variable> {
+        self.scopes
+            .iter()
+            .zip(self.limits.iter())
+            .rev()
+            .find_map(|(scope, limit)| scope.get(name).filter(|variable| variable.index < *limit))
+    }
+
+    fn is_inside(&self, kind: ScopeKind) -> bool {
+        for scope in self.scopes.iter().rev() {
+            if kind == scope.kind {
+                return true;
+            }
+            // a loop outside the function can not be broken from inside it
+            if ScopeKind::Function == scope.kind {
+                return false;
+            }
+        }
+        false
+    }
+
+    fn report(&mut self, err: CompilerError, statement: &AbstractSyntaxTree) {
+        self.errors.push(err.at(statement.span()));
+    }
+
+    fn resolve_statement(&mut self, statement: &AbstractSyntaxTree) {
+        match statement {
+            AbstractSyntaxTree::Literal(literal, _) => {
+                if let DataType::Function(function) = &literal.value {
+                    self.defer_function(function);
+                }
+            }
+            AbstractSyntaxTree::Identifier(name, _) => {
+                if !self.is_declared(name) && self.global.get_built_in_properties(name).is_none() {
+                    self.report(CompilerError::UndefinedVariable(name.clone()), statement);
+                }
+            }
+            AbstractSyntaxTree::InterpolatedString(parts, _) => {
+                for part in parts {
+                    self.resolve_statement(part);
+                }
+            }
+            AbstractSyntaxTree::ListExpression(items, _) => {
+                for item in items.iter() {
+                    self.resolve_statement(item);
+                }
+            }
+            AbstractSyntaxTree::MapExpression(entries, _) => {
+                for entry in entries.iter() {
+                    self.resolve_statement(&entry.value);
+                }
+            }
+            AbstractSyntaxTree::UnaryExpression(_, expression, _)
+            | AbstractSyntaxTree::ParenthesizedExpression(expression, _)
+            | AbstractSyntaxTree::MemberExpression(expression, _, _)
+            | AbstractSyntaxTree::ElseStatement(expression, _) => {
+                self.resolve_statement(expression);
+            }
+            AbstractSyntaxTree::BinaryExpression(left, _, right, _)
+            | AbstractSyntaxTree::IndexExpression(left, right, _) => {
+                self.resolve_statement(left);
+                self.resolve_statement(right);
+            }
+            AbstractSyntaxTree::RangeExpression(start, end, _, step, _) => {
+                self.resolve_statement(start);
+                self.resolve_statement(end);
+                if let Some(step) = step {
+                    self.resolve_statement(step);
+                }
+            }
+            AbstractSyntaxTree::AssignmentExpression(target, operator, expression, _) => {
+                self.resolve_statement(expression);
+                self.resolve_assignment(target, operator, statement);
+            }
+            AbstractSyntaxTree::MutableAssignmentExpression(name, operator, expression, _) => {
+                self.resolve_statement(expression);
+                match self.find_assignable(name) {
+                    Some(variable) if !variable.mutable => self.report(
+                        CompilerError::CannotConvertFromImmutableToMutable,
+                        statement,
+                    ),
+                    Some(_) => {}
+                    None if Operator::Assignment(Assingment::Simple) == *operator => {
+                        self.declare(name, true)
+                    }
+                    None if !self.is_declared(name) => {
+                        self.report(CompilerError::UndefinedVariable(name.clone()), statement)
+                    }
+                    None => {}
+                }
+            }
+            AbstractSyntaxTree::BlockStatement(block, _) => {
+                self.begin_scope(ScopeKind::Block);
+                for statement in block.read().unwrap().statements.iter() {
+                    self.resolve_statement(statement);
+                }
+                self.end_scope();
+            }
+            AbstractSyntaxTree::IfStatement(condition, if_statement, else_statement, _) => {
+                self.resolve_statement(condition);
+                // only one of the branches runs, neither sees what the other declares
+                let mut branches = vec![self.resolve_branch(if_statement)];
+                if let Some(else_statement) = else_statement {
+                    branches.push(self.resolve_branch(else_statement));
+                }
+                for branch in branches {
+                    for (name, variable) in branch.variables() {
+                        self.declare(name, variable.mutable);
+                    }
+                }
+            }
+            AbstractSyntaxTree::LoopStatement(condition, body, _) => {
+                self.resolve_statement(condition);
+                self.begin_scope(ScopeKind::Loop);
+                self.resolve_statement(body);
+                self.end_scope();
+            }
+            AbstractSyntaxTree::ForStatement(names, iterable, body, _) => {
+                self.resolve_statement(iterable);
+                self.begin_scope(ScopeKind::Loop);
+                for name in names {
+                    self.declare(name, false);
+                }
+                self.resolve_statement(body);
+                self.end_scope();
+            }
+            AbstractSyntaxTree::CallStatement(callee, arguements, _) => {
+                match callee.as_ref() {
+                    AbstractSyntaxTree::Identifier(name, _)
+                        if !self.is_declared(name)
+                            && self.global.get_built_in_properties(name).is_none() =>
+                    {
+                        self.report(CompilerError::UndefinedFunction(name.clone()), callee)
+                    }
+                    AbstractSyntaxTree::Identifier(..) => {}
+                    callee => self.resolve_statement(callee),
+                }
+                for arguement in arguements.iter() {
+                    self.resolve_statement(arguement);
+                }
+            }
+            AbstractSyntaxTree::ReturnStatement(expression, _) => {
+                self.resolve_statement(expression);
+                if !self.is_inside(ScopeKind::Function) {
+                    self.report(CompilerError::ReturnOutsideFunction, statement);
+                }
+            }
+            AbstractSyntaxTree::BreakStatement(expression, _) => {
+                self.resolve_statement(expression);
+                if !self.is_inside(ScopeKind::Loop) {
+                    self.report(CompilerError::BreakOutsideLoop, statement);
+                }
+            }
+            AbstractSyntaxTree::SkipStatement(expression, _) => {
+                self.resolve_statement(expression);
+                if !self.is_inside(ScopeKind::Loop) {
+                    self.report(CompilerError::SkipOutsideLoop, statement);
+                }
+            }
+        }
+    }
+
+    // a branch without braces runs in the enclosing scope, its variables are
+    // declared there once both branches are resolved
+    fn resolve_branch(&mut self, statement: &AbstractSyntaxTree) -> Scope {
+        self.begin_scope(ScopeKind::Block);
+        self.resolve_statement(statement);
+        self.end_scope()
+    }
+
+    fn resolve_assignment(
+        &mut self,
+        target: &AbstractSyntaxTree,
+        operator: &Operator,
+        statement: &AbstractSyntaxTree,
+    ) {
+        let AbstractSyntaxTree::Identifier(name, _) = target else {
+            // `xs[0] = v` and `user.name = v` change the variable they start from
+            self.resolve_statement(target);
+            if let Some(name) = target.assigned_variable() {
+                if let Some(variable) = self.find_assignable(name) {
+                    if !variable.mutable {
+                        let err = CompilerError::ImmutableVariable(name.to_string());
+                        self.report(err, statement);
+                    }
+                }
+            }
+            return;
+        };
+        match self.find_assignable(name) {
+            Some(variable) if !variable.mutable => {
+                self.report(CompilerError::ImmutableVariable(name.clone()), statement)
+            }
+            Some(_) => {}
+            None if Operator::Assignment(Assingment::Simple) == *operator => {
+                self.declare(name, false)
+            }
+            None if !self.is_declared(name) => {
+                self.report(CompilerError::UndefinedVariable(name.clone()), statement)
+            }
+            None => {}
+        }
+    }
+}
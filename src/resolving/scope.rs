@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::common::functions::Function;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ScopeKind {
+    Block,
+    Function,
+    Loop,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Variable {
+    pub(crate) mutable: bool,
+    // how many variables the scope had before this one
+    pub(crate) index: usize,
+}
+
+// a function literal whose body is resolved when its scope ends,
+// so that it can use the variables declared after it, like itself
+#[derive(Debug, Clone)]
+pub(crate) struct PendingFunction {
+    pub(crate) function: Arc<Function>,
+    // how many variables of every enclosing scope had been declared when it was created
+    pub(crate) limits: Vec<usize>,
+}
+
+// the variables a block, loop body or function declares, known before it runs
+#[derive(Debug, Clone)]
+pub(crate) struct Scope {
+    pub(crate) kind: ScopeKind,
+    variables: HashMap<String, Variable>,
+    pub(crate) functions: Vec<PendingFunction>,
+}
+
+impl Scope {
+    pub(crate) fn new(kind: ScopeKind) -> Self {
+        Self {
+            kind,
+            variables: HashMap::new(),
+            functions: Vec::new(),
+        }
+    }
+
+    pub(crate) fn declare(&mut self, name: &str, mutable: bool) {
+        if !self.variables.contains_key(name) {
+            let index = self.variables.len();
+            self.variables
+                .insert(name.to_string(), Variable { mutable, index });
+        }
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<Variable> {
+        self.variables.get(name).copied()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.variables.len()
+    }
+
+    // in the order they were declared
+    pub(crate) fn variables(&self) -> Vec<(&str, Variable)> {
+        let mut variables: Vec<_> = self
+            .variables
+            .iter()
+            .map(|(name, variable)| (name.as_str(), *variable))
+            .collect();
+        variables.sort_by_key(|(_, variable)| variable.index);
+        variables
+    }
+}
+
+impl Default for Scope {
+    fn default() -> Self {
+        Self::new(ScopeKind::Block)
+    }
+}
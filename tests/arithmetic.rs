@@ -0,0 +1,66 @@
+mod common;
+
+use common::{assert_errors, assert_values};
+use prolang::DataType;
+
+#[test]
+fn division_by_zero() {
+    assert_values(
+        "",
+        &[
+            ("7 / 0", DataType::Infinity),
+            ("-7 / 0", DataType::Infinity),
+            ("7.5 / 0", DataType::Infinity),
+            ("7 / 0.0", DataType::Infinity),
+            ("(1 / 0) / 2", DataType::Infinity),
+            ("(1 / 0) / 2.5", DataType::Infinity),
+            ("7 % 0", DataType::Infinity),
+        ],
+    );
+    assert_errors(&[
+        ("0 / 0", "Math Error: undefined at line 1, column 1"),
+        ("0.0 / 0", "Math Error: undefined at line 1, column 1"),
+        ("(1 / 0) / 0", "Math Error: undefined at line 1, column 1"),
+    ]);
+}
+
+#[test]
+fn integer_results() {
+    assert_values(
+        "",
+        &[
+            ("6 / 3", DataType::Float(2.0)),
+            ("7 / 2", DataType::Float(3.5)),
+            ("2 ** 10", DataType::Integer(1024)),
+            ("2 ** 126", DataType::Integer(1 << 126)),
+            ("(-1) ** 1001", DataType::Integer(-1)),
+            ("1 ** 1000000000000", DataType::Integer(1)),
+            (
+                "-170141183460469231731687303715884105727 - 1",
+                DataType::Integer(i128::MIN),
+            ),
+        ],
+    );
+}
+
+#[test]
+fn integer_overflow() {
+    assert_errors(&[
+        (
+            "170141183460469231731687303715884105727 + 1",
+            "Math Error: the result of + is too large for an integer at line 1, column 1",
+        ),
+        (
+            "-170141183460469231731687303715884105727 - 2",
+            "Math Error: the result of - is too large for an integer at line 1, column 1",
+        ),
+        (
+            "170141183460469231731687303715884105727 * 2",
+            "Math Error: the result of * is too large for an integer at line 1, column 1",
+        ),
+        (
+            "2 ** 200",
+            "Math Error: the result of ** is too large for an integer at line 1, column 1",
+        ),
+    ]);
+}
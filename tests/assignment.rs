@@ -0,0 +1,64 @@
+mod common;
+
+use common::{assert_equals, assert_errors};
+
+#[test]
+fn items_and_members() {
+    assert_equals(
+        "let xs = [1, 2, 3]\nlet user = { address: { city: \"paris\" } }\n",
+        &[
+            ("xs[0] = 10\nxs", "[10, 2, 3]"),
+            ("xs[-1] += 1\nxs", "[1, 2, 4]"),
+            (
+                "user.address.city = \"rome\"\nuser.address.city",
+                "\"rome\"",
+            ),
+            ("user[\"age\"] = 21\nuser.age", "21"),
+            (
+                "user.address[\"zip\"] = 1\nuser",
+                "{ address: { city: \"paris\", zip: 1 } }",
+            ),
+            ("xs[0] = [4]\nxs[0][0] *= 2\nxs", "[[8], 2, 3]"),
+        ],
+    );
+}
+
+#[test]
+fn changes_are_not_seen_by_other_values() {
+    assert_equals(
+        "let xs = [[1], [2]]\n",
+        &[
+            ("let ys = xs\nys[0][0] = 9\nxs", "[[1], [2]]"),
+            ("let ys = xs\nys[0][0] = 9\nys", "[[9], [2]]"),
+            ("ys = xs\nxs[1] = 0\nys", "[[1], [2]]"),
+            ("inner = xs[0]\nxs[0][0] = 5\ninner", "[1]"),
+            ("all = [xs]\nxs[0] = 0\nall", "[[[1], [2]]]"),
+            ("f = (v) => v\nkept = f(xs)\nxs[0] = 0\nkept", "[[1], [2]]"),
+            ("ys = xs\nxs += [[3]]\nys", "[[1], [2]]"),
+            ("xs += xs\nxs", "[[1], [2], [1], [2]]"),
+            ("xs[1] = xs\nxs", "[[1], [[1], [2]]]"),
+        ],
+    );
+}
+
+#[test]
+fn immutable_variables_and_invalid_targets() {
+    assert_errors(&[
+        (
+            "xs = [1]\nxs[0] = 2",
+            "cannot mutate Immutable variable 'xs' at line 2, column 1",
+        ),
+        (
+            "let s = \"ab\"\ns[0] = 1",
+            "cannot assign into a value of type String at line 2, column 1",
+        ),
+        (
+            "let xs = [1]\nxs[0][0] = 1",
+            "cannot assign into a value of type Integer at line 2, column 1",
+        ),
+        (
+            "let m = { a: 1 }\nm.b.c = 1",
+            "key 'b' is not in the map at line 2, column 1",
+        ),
+    ]);
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use common::assert_values;
+use prolang::DataType;
+
+// a function keeps the variables around it when it was created, and shares the `let` ones
+const FUNCTIONS: &str = "make_counter = () => {
+    let count = 0
+    () => { count += 1 }
+}
+memoize = (f) => {
+    let cache = {}
+    (n) => {
+        key = $\"{n}\"
+        if not has(cache, key) {
+            cache[key] = f(n)
+        }
+        cache[key]
+    }
+}
+let calls = 0
+square = (n) => {
+    calls += 1
+    n * n
+}
+make_adder = (a) => (b) => a + b
+on = (callbacks) => (event) => {
+    let results = []
+    loop for callback in callbacks {
+        results = results + [callback(event)]
+    }
+    results
+}
+";
+
+#[test]
+fn counters() {
+    assert_values(
+        FUNCTIONS,
+        &[
+            ("counter = make_counter()\ncounter()", DataType::Integer(1)),
+            (
+                "counter = make_counter()\ncounter(); counter()\ncounter()",
+                DataType::Integer(3),
+            ),
+            // every call of `make_counter` has its own `count`
+            (
+                "a = make_counter()\nb = make_counter()\na(); a(); b()\na() * 10 + b()",
+                DataType::Integer(32),
+            ),
+        ],
+    );
+}
+
+#[test]
+fn memoizers() {
+    assert_values(
+        FUNCTIONS,
+        &[
+            (
+                "fast = memoize(square)\nfast(4) + fast(4)",
+                DataType::Integer(32),
+            ),
+            (
+                "fast = memoize(square)\nfast(4); fast(4); fast(5); fast(4)\ncalls",
+                DataType::Integer(2),
+            ),
+            (
+                "a = memoize(square)\nb = memoize(square)\na(3); b(3)\ncalls",
+                DataType::Integer(2),
+            ),
+        ],
+    );
+}
+
+#[test]
+fn callback_factories() {
+    assert_values(
+        FUNCTIONS,
+        &[
+            ("make_adder(1)(2)", DataType::Integer(3)),
+            (
+                "add_ten = make_adder(10)\nadd_one = make_adder(1)\nadd_ten(5) - add_one(5)",
+                DataType::Integer(9),
+            ),
+            (
+                "handle = on([make_adder(1), make_adder(100), square])\nhandle(3) == [4, 103, 9]",
+                DataType::Boolean(true),
+            ),
+            // every round of a for loop has its own variables, so the callbacks keep different values
+            (
+                "let callbacks = []
+            loop for i in 0..3 {
+                callbacks = callbacks + [() => i * 10]
+            }
+            callbacks[0]() + callbacks[2]()",
+                DataType::Integer(20),
+            ),
+        ],
+    );
+}
+
+#[test]
+fn closures_outlive_their_block() {
+    assert_values(
+        FUNCTIONS,
+        &[
+            (
+                "get = if true { secret = 42; () => secret }\nget()",
+                DataType::Integer(42),
+            ),
+            (
+                "make = () => { let n = 0; [() => { n += 1 }, { read: () => n }] }
+            pair = make()
+            pair[0](); pair[0]()
+            pair[1].read()",
+                DataType::Integer(2),
+            ),
+        ],
+    );
+}
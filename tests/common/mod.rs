@@ -0,0 +1,46 @@
+// helpers shared by the integration tests, not every test uses all of them
+#![allow(dead_code)]
+
+use prolang::{DataType, Interpreter};
+
+// runs `prelude` followed by every program in a new interpreter,
+// and compares the value of the last statement with the expected one
+pub fn assert_values(prelude: &str, cases: &[(&str, DataType)]) {
+    for (program, expected) in cases {
+        let source = format!("{prelude}{program}");
+        let result = Interpreter::new()
+            .evaluate(&source)
+            .unwrap_or_else(|err| panic!("`{program}` failed: {err}"));
+        assert_eq!(*result.value(), *expected, "`{program}`");
+    }
+}
+
+// like `assert_values`, but the expected value is prolang code run after the program
+pub fn assert_equals(prelude: &str, cases: &[(&str, &str)]) {
+    for (program, expected) in cases {
+        let interpreter = Interpreter::new();
+        let result = interpreter
+            .evaluate(&format!("{prelude}{program}"))
+            .unwrap_or_else(|err| panic!("`{program}` failed: {err}"));
+        let expected_value = interpreter
+            .evaluate(expected)
+            .unwrap_or_else(|err| panic!("`{expected}` failed: {err}"));
+        assert_eq!(
+            result.value(),
+            expected_value.value(),
+            "`{program}` gave {result}, expected {expected}"
+        );
+    }
+}
+
+// runs every program in a new interpreter, and compares the message of the error it fails with.
+// lexical, syntax and resolution errors come from the check before anything runs,
+// the others from running it
+pub fn assert_errors(cases: &[(&str, &str)]) {
+    for (program, expected) in cases {
+        let err = Interpreter::new()
+            .evaluate(program)
+            .expect_err(&format!("`{program}` passed"));
+        assert_eq!(err.to_string(), *expected, "`{program}`");
+    }
+}
@@ -0,0 +1,188 @@
+mod common;
+
+use common::{assert_errors, assert_values};
+use prolang::{DataType, Interpreter};
+use std::io::{self, Cursor, ErrorKind, Read};
+use std::sync::Arc;
+
+fn string(text: &str) -> DataType {
+    DataType::String(Arc::new(text.to_string()))
+}
+
+#[test]
+fn escape_sequences() {
+    assert_values(
+        "",
+        &[
+            (r#""a\tb\n""#, string("a\tb\n")),
+            (r#""\\ \" \' \0""#, string("\\ \" ' \0")),
+            (r#""\u{1F600}\u{e9}""#, string("😀é")),
+            (r#"$"{1}\u{21}""#, string("1!")),
+        ],
+    );
+}
+
+#[test]
+fn invalid_escape_sequences() {
+    assert_errors(&[
+        (
+            r#"print("\q")"#,
+            r"Invalid escape sequence '\q' at line 1, column 8",
+        ),
+        // the quote after the escape still ends the string
+        (
+            r#"print("\u{12")"#,
+            r"Invalid escape sequence '\u{12' at line 1, column 8",
+        ),
+        (
+            r#"print("\u")"#,
+            r"Invalid escape sequence '\u' at line 1, column 8",
+        ),
+        (
+            r#"print("\u{12 x")"#,
+            r"Invalid escape sequence '\u{12' at line 1, column 8",
+        ),
+        (
+            r#"print("\u{}")"#,
+            r"Invalid escape sequence '\u{}' at line 1, column 8",
+        ),
+        (
+            r#"print("\u{1234567}")"#,
+            r"Invalid escape sequence '\u{1234567}' at line 1, column 8",
+        ),
+        (
+            r#"print("\u{110000}")"#,
+            r"Invalid escape sequence '\u{110000}' at line 1, column 8",
+        ),
+        (
+            r#"print($"a\u{12")"#,
+            r"Invalid escape sequence '\u{12' at line 1, column 10",
+        ),
+        (
+            "x = \"\"\"\n\\u{1\n\"\"\"",
+            r"Invalid escape sequence '\u{1' at line 2, column 1",
+        ),
+    ]);
+}
+
+#[test]
+fn unterminated_strings_and_comments() {
+    assert_errors(&[
+        ("print(\"abc", "Unterminated string at line 1, column 7"),
+        ("print('abc\")", "Unterminated string at line 1, column 7"),
+        ("x = $\"a {1}", "Unterminated string at line 1, column 5"),
+        (
+            "x = \"\"\"\nabc\"\"",
+            "Unterminated string at line 1, column 5",
+        ),
+        (
+            "#[ open #[ nested ]#",
+            "Unterminated block comment at line 1, column 1",
+        ),
+    ]);
+}
+
+#[test]
+fn invalid_characters_and_numbers() {
+    assert_errors(&[
+        ("print(1 @ 2)", "Invalid character '@' at line 1, column 9"),
+        ("x = 1 ~ 2", "Invalid character '~' at line 1, column 7"),
+        ("x = $1", "Invalid character '$' at line 1, column 5"),
+        ("x = 1\r", r"Invalid character '\r' at line 1, column 6"),
+        ("x = 0x", "Invalid number '0x' at line 1, column 5"),
+        ("x = 0b12", "Invalid number '0b12' at line 1, column 5"),
+    ]);
+}
+
+#[test]
+fn every_lexical_error_is_reported() {
+    assert_errors(&[(
+        "print(\"\\q\", 1 @ 2)\nx = 0x",
+        "Invalid escape sequence '\\q' at line 1, column 8
+Invalid character '@' at line 1, column 15
+Invalid number '0x' at line 2, column 5",
+    )]);
+}
+
+#[test]
+fn raw_string_hashes() {
+    assert_values(
+        "r = 1\n",
+        &[
+            ("r#######\"a\"#\"#######", string("a\"#")),
+            // `#`s that are not followed by a quote start a comment
+            ("r#######x", DataType::Integer(1)),
+        ],
+    );
+    assert_errors(&[
+        (
+            "x = r########\"a\"########\nprint(x)",
+            "Raw strings can start with at most 7 `#`s at line 1, column 5",
+        ),
+        (
+            "r = 1\nprint(r##########x)",
+            "Raw strings can start with at most 7 `#`s at line 2, column 7",
+        ),
+    ]);
+}
+
+#[test]
+fn invalid_utf8() {
+    let source = b"print(\"\xff\xfe\") \xc3 x".to_vec();
+    let err = Interpreter::new()
+        .evaluate_reader(Cursor::new(source))
+        .unwrap_err();
+    // a run of bad bytes is one error, every bad byte takes up a column
+    assert_eq!(
+        err.to_string(),
+        "Invalid UTF-8 character at line 1, column 8
+Invalid UTF-8 character at line 1, column 13
+Expected a new line or `;` before 'x' at line 1, column 15"
+    );
+}
+
+// gives the source in pieces, failing with the given errors before them
+struct FlakyReader {
+    reads: Vec<Result<&'static [u8], ErrorKind>>,
+}
+
+impl Read for FlakyReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.reads.is_empty() {
+            return Ok(0);
+        }
+        match self.reads.remove(0) {
+            Ok(bytes) => {
+                buf[..bytes.len()].copy_from_slice(bytes);
+                Ok(bytes.len())
+            }
+            Err(kind) => Err(io::Error::new(kind, "flaky")),
+        }
+    }
+}
+
+#[test]
+fn interrupted_reads_are_retried() {
+    let reader = FlakyReader {
+        reads: vec![
+            Err(ErrorKind::Interrupted),
+            Ok(b"x = 4"),
+            Err(ErrorKind::Interrupted),
+            Ok(b"0 + 2"),
+        ],
+    };
+    let result = Interpreter::new().evaluate_reader(reader).unwrap();
+    assert_eq!(*result.value(), DataType::Integer(42));
+}
+
+#[test]
+fn failed_reads_are_reported() {
+    let reader = FlakyReader {
+        reads: vec![
+            Ok(b"print(\"never runs\")\nx = \"abc"),
+            Err(ErrorKind::Other),
+        ],
+    };
+    let err = Interpreter::new().evaluate_reader(reader).unwrap_err();
+    assert_eq!(err.to_string(), "Unable to read the source: flaky");
+}
@@ -0,0 +1,199 @@
+mod common;
+
+use common::{assert_equals, assert_errors};
+
+// every loop adds what it sees to `seen`
+const SEEN: &str = "let seen = []\n";
+
+fn assert_rounds(cases: &[(&str, &str)]) {
+    for (program, expected) in cases {
+        assert_equals(SEEN, &[(&format!("{program}\nseen"), expected)]);
+    }
+}
+
+#[test]
+fn ranges() {
+    assert_rounds(&[
+        ("loop for i in 0..4 seen = seen + [i]", "[0, 1, 2, 3]"),
+        ("loop for i in 0..=4 seen = seen + [i]", "[0, 1, 2, 3, 4]"),
+        ("loop for i in 3..3 seen = seen + [i]", "[]"),
+        ("loop for i in 3..=3 seen = seen + [i]", "[3]"),
+        ("loop for i in 4..0 seen = seen + [i]", "[]"),
+        (
+            "n = 3\nloop for i in n - 1..n * 2 seen = seen + [i]",
+            "[2, 3, 4, 5]",
+        ),
+        (
+            "loop for index, i in 5..8 seen = seen + [index, i]",
+            "[0, 5, 1, 6, 2, 7]",
+        ),
+    ]);
+}
+
+#[test]
+fn stepped_ranges() {
+    assert_rounds(&[
+        (
+            "loop for i in 0..10 step 3 seen = seen + [i]",
+            "[0, 3, 6, 9]",
+        ),
+        (
+            "loop for i in 0..=9 step 3 seen = seen + [i]",
+            "[0, 3, 6, 9]",
+        ),
+        ("loop for i in 0..9 step 3 seen = seen + [i]", "[0, 3, 6]"),
+        (
+            "loop for i in 4..0 step -1 seen = seen + [i]",
+            "[4, 3, 2, 1]",
+        ),
+        ("loop for i in 4..=0 step -2 seen = seen + [i]", "[4, 2, 0]"),
+        ("loop for i in 0..4 step -1 seen = seen + [i]", "[]"),
+    ]);
+}
+
+#[test]
+fn ranges_outside_a_loop_are_lists() {
+    assert_rounds(&[
+        ("seen = 1..4", "[1, 2, 3]"),
+        ("seen = 0..=6 step 3", "[0, 3, 6]"),
+        ("seen = 3..0 step -1", "[3, 2, 1]"),
+    ]);
+}
+
+#[test]
+fn parenthesized_ranges() {
+    assert_rounds(&[
+        ("seen = (0..3)", "[0, 1, 2]"),
+        ("seen = (1..3) + [9]", "[1, 2, 9]"),
+        ("seen = ((0..=4 step 2))", "[0, 2, 4]"),
+        ("loop for i in (0..3) seen = seen + [i]", "[0, 1, 2]"),
+        // a loop does not make a list of the range, however long it is
+        (
+            "loop for i in (0..1000000000000) { if i == 3 { break }; seen = seen + [i] }",
+            "[0, 1, 2]",
+        ),
+    ]);
+}
+
+#[test]
+fn long_ranges() {
+    assert_rounds(&[
+        ("seen = len(0..=9999999)", "10000000"),
+        ("seen = len(10..=-10 step -3)", "7"),
+        (
+            "seen = 170141183460469231731687303715884105720..=170141183460469231731687303715884105727 step 3",
+            "[170141183460469231731687303715884105720, 170141183460469231731687303715884105723, 170141183460469231731687303715884105726]",
+        ),
+    ]);
+    assert_errors(&[
+        (
+            "x = 0..1000000000",
+            "a range of more than 10000000 numbers is too long for a list at line 1, column 5",
+        ),
+        (
+            "x = (-170141183460469231731687303715884105727 - 1)..=170141183460469231731687303715884105727",
+            "a range of more than 10000000 numbers is too long for a list at line 1, column 5",
+        ),
+    ]);
+}
+
+#[test]
+fn strings_lists_and_maps() {
+    assert_rounds(&[
+        (
+            "loop for c in \"abc\" seen = seen + [c]",
+            "[\"a\", \"b\", \"c\"]",
+        ),
+        (
+            "loop for i, c in \"hé\" seen = seen + [i, c]",
+            "[0, \"h\", 1, \"é\"]",
+        ),
+        ("loop for item in [10, 20] seen = seen + [item]", "[10, 20]"),
+        (
+            "loop for index, item in [10, 20] seen = seen + [index, item]",
+            "[0, 10, 1, 20]",
+        ),
+        ("loop for item in [] seen = seen + [item]", "[]"),
+        (
+            "loop for key in { a: 1, b: 2 } seen = seen + [key]",
+            "[\"a\", \"b\"]",
+        ),
+        (
+            "loop for key, value in { a: 1, b: 2 } seen = seen + [key, value]",
+            "[\"a\", 1, \"b\", 2]",
+        ),
+    ]);
+}
+
+#[test]
+fn break_and_skip() {
+    assert_rounds(&[
+        (
+            "loop for i in 0..10 { if i == 3 { break }; seen = seen + [i] }",
+            "[0, 1, 2]",
+        ),
+        (
+            "seen = loop for i in 0..10 { if i * i > 20 { break i } }",
+            "5",
+        ),
+        (
+            "loop for i in 0..6 { if i % 2 == 1 { skip 0 }; seen = seen + [i] }",
+            "[0, 2, 4]",
+        ),
+        // a skip count of n also leaves out the next n rounds
+        (
+            "loop for i in 0..6 { if i == 1 { skip }; seen = seen + [i] }",
+            "[0, 3, 4, 5]",
+        ),
+        (
+            "loop for i in 0..6 { if i == 1 { skip 2 }; seen = seen + [i] }",
+            "[0, 4, 5]",
+        ),
+        // only the innermost loop ends
+        (
+            "loop for i in 0..3 loop for j in 0..3 { if j == 1 { break }; seen = seen + [i * 10 + j] }",
+            "[0, 10, 20]",
+        ),
+        (
+            "f = () => { loop for i in 0..10 { if i == 4 { return i } }; 99 }\nseen = f()",
+            "4",
+        ),
+    ]);
+}
+
+#[test]
+fn for_in_and_step_can_name_variables() {
+    assert_rounds(&[
+        (
+            "step = 2\nloop for i in 0..5 step step seen = seen + [i]",
+            "[0, 2, 4]",
+        ),
+        (
+            "in = [1, 2]\nloop for for in in seen = seen + [for]",
+            "[1, 2]",
+        ),
+        ("for = (x) => x * 10\nseen = for(2)", "20"),
+    ]);
+}
+
+#[test]
+fn invalid_loops() {
+    assert_errors(&[
+        (
+            "loop for i in 0..3 step 0 print(i)",
+            "the step of a range can not be 0 at line 1, column 1",
+        ),
+        (
+            "loop for i in 0..1.5 print(i)",
+            "range bounds and steps must be integers, but got Float at line 1, column 1",
+        ),
+        (
+            "loop for i in 5 print(i)",
+            "cannot loop over a value of type Integer at line 1, column 1",
+        ),
+        (
+            "loop for i in [1] { i = 2 }",
+            "cannot mutate Immutable variable 'i' at line 1, column 21",
+        ),
+    ]);
+}
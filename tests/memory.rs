@@ -0,0 +1,62 @@
+use prolang::Interpreter;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// counts the bytes in use, this file has a single test so nothing else runs alongside it
+struct Counting;
+
+static IN_USE: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        IN_USE.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+// every one of these leaves a function in the environment it holds on to
+const FUNCTIONS: &str = "make = (n) => {
+    let fs = []
+    loop for i in 0..n { fs += [() => i] }
+    fs
+}
+outer = (n) => {
+    count = (k) => if k == 0 { 0 } else { 1 + count(k - 1) }
+    count(n)
+}
+make_counter = () => {
+    let count = 0
+    counter = () => { count += 1 }
+    counter
+}";
+
+const ROUNDS: &str = "loop for round in 0..200 {
+    make(10)
+    outer(5)
+    { f = () => f; f }
+    counter = make_counter()
+    counter()
+}";
+
+#[test]
+fn closures_are_freed() {
+    let interpreter = Interpreter::new();
+    interpreter.evaluate(FUNCTIONS).unwrap();
+    interpreter.evaluate(ROUNDS).unwrap();
+    let before = IN_USE.load(Ordering::Relaxed);
+    for _ in 0..20 {
+        interpreter.evaluate(ROUNDS).unwrap();
+    }
+    let after = IN_USE.load(Ordering::Relaxed);
+    // the runs that are not freed yet are few and small
+    let grown = after.saturating_sub(before);
+    assert!(grown < 1024 * 1024, "{grown} bytes are still in use");
+}
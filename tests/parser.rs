@@ -0,0 +1,45 @@
+mod common;
+
+use common::assert_errors;
+
+#[test]
+fn every_syntax_error_is_reported_once() {
+    assert_errors(&[
+        ("x = }", "Unexpected token '}' at line 1, column 5"),
+        ("x = 1 + }", "Unexpected token '}' at line 1, column 9"),
+        (
+            "x = 1 + }\nprint(2 +)",
+            "Unexpected token '}' at line 1, column 9
+Unexpected token ')' at line 2, column 10",
+        ),
+        (
+            "}\nx = 1 +",
+            "Unexpected token '}' at line 1, column 1
+Unexpected token 'end of the file' at line 2, column 8",
+        ),
+        // a `}` in a block still closes it
+        (
+            "if true { x = }\nprint(1 +)",
+            "Unexpected token '}' at line 1, column 15
+Unexpected token ')' at line 2, column 10",
+        ),
+    ]);
+}
+
+#[test]
+fn unclosed_arguments() {
+    assert_errors(&[
+        (
+            "print(1",
+            "Unexpected token 'end of the file' at line 1, column 8. Expected )",
+        ),
+        (
+            "print(1 2)",
+            "Unexpected token '2' at line 1, column 9. Expected ,",
+        ),
+        (
+            "x = [1, 2",
+            "Unexpected token 'end of the file' at line 1, column 10. Expected ]",
+        ),
+    ]);
+}
@@ -0,0 +1,182 @@
+use prolang::{DataType, Interpreter};
+
+// the binding power and associativity of every binary operator, as documented in the README
+const BINARY: [(&str, u8, bool); 17] = [
+    ("or", 1, false),
+    ("xor", 2, false),
+    ("and", 3, false),
+    ("==", 5, false),
+    ("!=", 5, false),
+    ("is", 5, false),
+    ("is not", 5, false),
+    ("<", 6, false),
+    ("<=", 6, false),
+    (">", 6, false),
+    (">=", 6, false),
+    ("+", 7, false),
+    ("-", 7, false),
+    ("*", 8, false),
+    ("/", 8, false),
+    ("%", 8, false),
+    ("**", 10, true),
+];
+
+const PREFIX: [(&str, u8); 3] = [("not ", 4), ("-", 9), ("+", 9)];
+
+// operands picked so that the two ways of grouping an expression give different values
+const OPERANDS: [[&str; 3]; 20] = [
+    ["2", "3", "4"],
+    ["4", "3", "2"],
+    ["3", "2", "2"],
+    ["2", "2", "3"],
+    ["0", "1", "1"],
+    ["1", "0", "0"],
+    ["1", "1", "0"],
+    ["0", "0", "1"],
+    ["1", "1", "1"],
+    ["true", "false", "false"],
+    ["false", "true", "true"],
+    ["5", "5", "true"],
+    ["false", "false", "false"],
+    ["true", "true", "true"],
+    ["true", "false", "true"],
+    ["false", "true", "false"],
+    ["0", "2", "1"],
+    ["3", "0", "2"],
+    ["1", "-1", "5"],
+    ["1", "2", "false"],
+];
+
+// pairs that give the same value however they are grouped, so no operands can tell
+const ASSOCIATIVE: [(&str, &str); 15] = [
+    ("or", "or"),
+    ("xor", "xor"),
+    ("and", "and"),
+    ("!=", "!="),
+    ("!=", "is not"),
+    ("is not", "!="),
+    ("is not", "is not"),
+    ("+", "+"),
+    ("+", "-"),
+    ("*", "*"),
+    ("*", "/"),
+    ("not ", "xor"),
+    ("-", "*"),
+    ("-", "/"),
+    ("-", "%"),
+];
+
+fn evaluate(source: &str) -> Option<DataType> {
+    Interpreter::new()
+        .evaluate(source)
+        .ok()
+        .map(|value| value.value().clone())
+}
+
+// operands that one of the operators can not take, like `0 ** 0` or `true ** false`,
+// are left out, whichever way the expression is grouped
+fn with_valid_operands(cases: Vec<(String, String, String)>) -> Vec<(String, String, String)> {
+    cases
+        .into_iter()
+        .filter(|(_, expected, other)| evaluate(expected).is_some() && evaluate(other).is_some())
+        .collect()
+}
+
+// checks that `source` always gives the value of `expected`,
+// and that `other` gives something else at least once unless the pair is associative
+fn assert_grouping(pair: (&str, &str), cases: &[(String, String, String)]) {
+    assert!(!cases.is_empty(), "no operands are valid for {pair:?}");
+    let mut told_apart = false;
+    for (source, expected, other) in cases {
+        let expected_value = Interpreter::new()
+            .evaluate(expected)
+            .unwrap_or_else(|err| panic!("`{expected}` failed: {err}"))
+            .value()
+            .clone();
+        assert_eq!(
+            evaluate(source),
+            Some(expected_value.clone()),
+            "`{source}` should be `{expected}`"
+        );
+        told_apart |= evaluate(other) != Some(expected_value);
+    }
+    let (source, expected, _) = &cases[0];
+    assert!(
+        told_apart || ASSOCIATIVE.contains(&pair),
+        "no operands tell `{source}` from `{expected}`"
+    );
+}
+
+#[test]
+fn binary_operator_pairs() {
+    for (first, first_power, _) in BINARY {
+        for (second, second_power, second_right) in BINARY {
+            // the first operator takes `b` when it binds at least as tightly,
+            // unless both are the same right associative level
+            let first_wins =
+                first_power > second_power || (first_power == second_power && !second_right);
+            let cases = OPERANDS
+                .iter()
+                .map(|[a, b, c]| {
+                    let source = format!("{a} {first} {b} {second} {c}");
+                    let left = format!("({a} {first} {b}) {second} {c}");
+                    let right = format!("{a} {first} ({b} {second} {c})");
+                    if first_wins {
+                        (source, left, right)
+                    } else {
+                        (source, right, left)
+                    }
+                })
+                .collect();
+            let cases = with_valid_operands(cases);
+            assert_grouping((first, second), &cases);
+        }
+    }
+}
+
+#[test]
+fn prefix_and_binary_operators() {
+    for (prefix, prefix_power) in PREFIX {
+        for (operator, power, _) in BINARY {
+            let cases = OPERANDS
+                .iter()
+                .map(|[a, b, _]| {
+                    let source = format!("{prefix}{a} {operator} {b}");
+                    let inner = format!("{prefix}({a} {operator} {b})");
+                    let outer = format!("({prefix}{a}) {operator} {b}");
+                    if power > prefix_power {
+                        (source, inner, outer)
+                    } else {
+                        (source, outer, inner)
+                    }
+                })
+                .collect();
+            let cases = with_valid_operands(cases);
+            // `+` in front of a number changes nothing, whichever way it is grouped
+            if prefix != "+" {
+                assert_grouping((prefix, operator), &cases);
+            } else {
+                for (source, expected, _) in &cases {
+                    assert_eq!(evaluate(source), evaluate(expected), "`{source}`");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn exponentiation_is_right_associative() {
+    assert_eq!(evaluate("2 ** 3 ** 2"), Some(DataType::Integer(512)));
+    assert_eq!(evaluate("-2 ** 2"), Some(DataType::Integer(-4)));
+    assert_eq!(evaluate("10 - 3 - 2"), Some(DataType::Integer(5)));
+}
+
+#[test]
+fn not_negates_truthiness() {
+    assert_eq!(evaluate("not 0"), Some(DataType::Boolean(true)));
+    assert_eq!(evaluate("not 2"), Some(DataType::Boolean(false)));
+    assert_eq!(evaluate("not \"\""), Some(DataType::Boolean(true)));
+    assert_eq!(evaluate("not 0.0"), Some(DataType::Boolean(true)));
+    assert_eq!(evaluate("not not 5"), Some(DataType::Boolean(true)));
+    assert_eq!(evaluate("not 1 == 2"), Some(DataType::Boolean(true)));
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use common::{assert_errors, assert_values};
+use prolang::DataType;
+
+// every call gets its own frame, so a function can call itself or the functions declared after it
+const FUNCTIONS: &str = "fib = (n) => if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+sum = (n) => if n == 0 { 0 } else { n + sum(n - 1) }
+factorial = (n) => {
+    if n < 2 {
+        return 1
+    }
+    n * factorial(n - 1)
+}
+is_even = (n) => if n == 0 { true } else { is_odd(n - 1) }
+is_odd = (n) => if n == 0 { false } else { is_even(n - 1) }
+";
+
+#[test]
+fn fibonacci() {
+    assert_values(
+        FUNCTIONS,
+        &[
+            ("fib(0)", DataType::Integer(0)),
+            ("fib(1)", DataType::Integer(1)),
+            ("fib(10)", DataType::Integer(55)),
+            ("fib(15)", DataType::Integer(610)),
+        ],
+    );
+}
+
+#[test]
+fn calls_keep_the_parameters_of_their_caller() {
+    assert_values(
+        FUNCTIONS,
+        &[
+            ("sum(100)", DataType::Integer(5050)),
+            ("factorial(1)", DataType::Integer(1)),
+            ("factorial(20)", DataType::Integer(2432902008176640000)),
+            ("fib(5) + sum(3)", DataType::Integer(11)),
+        ],
+    );
+}
+
+#[test]
+fn mutual_recursion() {
+    assert_values(
+        FUNCTIONS,
+        &[
+            ("is_even(0)", DataType::Boolean(true)),
+            ("is_even(10)", DataType::Boolean(true)),
+            ("is_even(7)", DataType::Boolean(false)),
+            ("is_odd(7)", DataType::Boolean(true)),
+            ("is_odd(10)", DataType::Boolean(false)),
+        ],
+    );
+}
+
+#[test]
+fn local_recursive_functions() {
+    assert_values(
+        FUNCTIONS,
+        &[(
+            "outer = (n) => { count = (k) => if k == 0 { 0 } else { 1 + count(k - 1) }; count(n) }
+            outer(25)",
+            DataType::Integer(25),
+        )],
+    );
+}
+
+#[test]
+fn deep_recursion() {
+    assert_values(FUNCTIONS, &[("sum(999)", DataType::Integer(499500))]);
+    // fails instead of running out of stack
+    assert_errors(&[
+        (
+            "f = (n) => { if n == 0 { return 0 }; return f(n - 1) + 1 }\nf(50000)",
+            "calls can not be nested more than 1000 deep at line 1, column 45",
+        ),
+        (
+            "forever = () => forever()\nforever()",
+            "calls can not be nested more than 1000 deep at line 1, column 17",
+        ),
+    ]);
+}
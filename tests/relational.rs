@@ -0,0 +1,205 @@
+use prolang::{DataType, Interpreter};
+
+// the functions `f` and `g` are the same, `h` is a different function with the same body
+const FUNCTIONS: &str = "f = (a) => a
+g = f
+h = (a) => a
+";
+
+fn assert_relations(operator: &str, cases: &[(&str, &str, bool)]) {
+    for (left, right, expected) in cases {
+        let source = format!("{FUNCTIONS}{left} {operator} {right}");
+        let result = Interpreter::new()
+            .evaluate(&source)
+            .unwrap_or_else(|err| panic!("`{left} {operator} {right}` failed: {err}"));
+        assert_eq!(
+            *result.value(),
+            DataType::Boolean(*expected),
+            "`{left} {operator} {right}`"
+        );
+    }
+}
+
+#[test]
+fn equality() {
+    let cases = [
+        ("1", "1", true),
+        ("1", "2", false),
+        ("1.5", "1.5", true),
+        ("1.5", "2.5", false),
+        ("\"a\"", "\"a\"", true),
+        ("\"a\"", "\"b\"", false),
+        ("true", "true", true),
+        ("true", "false", false),
+        ("1.0 / 0", "1.0 / 0", true),
+        ("1", "1.0", true),
+        ("1", "1.5", false),
+        ("4 / 2", "2", true),
+        ("2.5", "10 / 4", true),
+        ("1", "\"1\"", false),
+        ("1", "true", false),
+        ("f", "f", true),
+        ("f", "g", true),
+        ("f", "h", false),
+        ("f", "1", false),
+    ];
+    assert_relations("==", &cases);
+}
+
+#[test]
+fn inequality() {
+    let cases = [
+        ("1", "1", false),
+        ("1", "2", true),
+        ("1.5", "1.5", false),
+        ("1.5", "2.5", true),
+        ("\"a\"", "\"a\"", false),
+        ("\"a\"", "\"b\"", true),
+        ("true", "true", false),
+        ("true", "false", true),
+        ("1.0 / 0", "1.0 / 0", false),
+        ("1", "1.0", false),
+        ("1", "1.5", true),
+        ("4 / 2", "2", false),
+        ("1", "\"1\"", true),
+        ("f", "g", false),
+        ("f", "h", true),
+    ];
+    assert_relations("!=", &cases);
+}
+
+#[test]
+fn identity() {
+    let cases = [
+        ("1", "1", true),
+        ("1", "2", false),
+        ("1.5", "1.5", true),
+        ("\"a\"", "\"a\"", true),
+        ("\"a\"", "\"b\"", false),
+        ("true", "true", true),
+        ("true", "false", false),
+        ("1.0 / 0", "1.0 / 0", true),
+        ("1", "1.0", true),
+        ("1", "1.5", false),
+        ("1", "\"1\"", false),
+        ("f", "f", true),
+        ("f", "g", true),
+        ("f", "h", false),
+        ("f", "1", false),
+    ];
+    assert_relations("is", &cases);
+}
+
+#[test]
+fn non_identity() {
+    let cases = [
+        ("1", "1", false),
+        ("1", "2", true),
+        ("1.5", "1.5", false),
+        ("\"a\"", "\"a\"", false),
+        ("\"a\"", "\"b\"", true),
+        ("true", "false", true),
+        ("1.0 / 0", "1.0 / 0", false),
+        ("1", "1.0", false),
+        ("1", "1.5", true),
+        ("f", "g", false),
+        ("f", "h", true),
+    ];
+    assert_relations("is not", &cases);
+}
+
+#[test]
+fn less_than() {
+    let cases = [
+        ("1", "2", true),
+        ("2", "1", false),
+        ("1", "1", false),
+        ("1.5", "2.5", true),
+        ("2.5", "1.5", false),
+        ("\"a\"", "\"b\"", true),
+        ("\"b\"", "\"a\"", false),
+        ("false", "true", true),
+        ("true", "false", false),
+        ("1.0 / 0", "1.0 / 0", false),
+        ("1", "2.0", true),
+        ("1", "2.5", true),
+        ("3", "2.5", false),
+        ("2.5", "3", true),
+        ("10 / 4", "3", true),
+        ("1", "1.0", false),
+    ];
+    assert_relations("<", &cases);
+}
+
+#[test]
+fn less_than_or_equals() {
+    let cases = [
+        ("1", "2", true),
+        ("1", "1", true),
+        ("2", "1", false),
+        ("1.5", "1.5", true),
+        ("\"a\"", "\"a\"", true),
+        ("\"b\"", "\"a\"", false),
+        ("false", "true", true),
+        ("true", "true", true),
+        ("true", "false", false),
+        ("1.0 / 0", "1.0 / 0", true),
+        ("1", "2.0", true),
+        ("1", "1.0", true),
+        ("3", "2.5", false),
+        ("2.5", "2", false),
+    ];
+    assert_relations("<=", &cases);
+}
+
+#[test]
+fn greater_than() {
+    let cases = [
+        ("2", "1", true),
+        ("1", "2", false),
+        ("1", "1", false),
+        ("2.5", "1.5", true),
+        ("\"b\"", "\"a\"", true),
+        ("\"a\"", "\"b\"", false),
+        ("true", "false", true),
+        ("false", "true", false),
+        ("1.0 / 0", "1.0 / 0", false),
+        ("2", "1.0", true),
+        ("3", "2.5", true),
+        ("1", "2.5", false),
+        ("2.5", "2", true),
+        ("1", "1.0", false),
+    ];
+    assert_relations(">", &cases);
+}
+
+#[test]
+fn greater_than_or_equals() {
+    let cases = [
+        ("2", "1", true),
+        ("1", "1", true),
+        ("1", "2", false),
+        ("1.5", "1.5", true),
+        ("\"b\"", "\"b\"", true),
+        ("\"a\"", "\"b\"", false),
+        ("true", "false", true),
+        ("false", "false", true),
+        ("false", "true", false),
+        ("1.0 / 0", "1.0 / 0", true),
+        ("2", "1.0", true),
+        ("1", "1.0", true),
+        ("1", "2.5", false),
+        ("1.5", "2", false),
+    ];
+    assert_relations(">=", &cases);
+}
+
+#[test]
+fn is_not_binds_like_inequality() {
+    let cases = [
+        ("1 + 1", "2", false),
+        ("1 + 1", "3", true),
+        ("1", "2 and true", true),
+    ];
+    assert_relations("is not", &cases);
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::assert_errors;
+use prolang::Interpreter;
+
+// every program is checked before any of it runs, the error names the problem and where it is
+#[test]
+fn undefined_names() {
+    assert_errors(&[
+        (
+            "a = 1\nprint(b)",
+            "Undefined variable 'b' at line 2, column 7",
+        ),
+        ("g = () => z", "Undefined variable 'z' at line 1, column 11"),
+        (
+            "if true {\n    w = 1\n}\nw",
+            "Undefined variable 'w' at line 4, column 1",
+        ),
+        ("x = foo(1)", "Undefined function foo at line 1, column 5"),
+    ]);
+}
+
+#[test]
+fn names_declared_after_a_function() {
+    let interpreter = Interpreter::new();
+    assert!(interpreter.check("f = () => y\ny = 1\nf()").is_ok());
+}
+
+#[test]
+fn return_outside_a_function() {
+    assert_errors(&[
+        (
+            "return 1",
+            "return statement can only occur inside a function at line 1, column 1",
+        ),
+        (
+            "if true {\n    return\n}",
+            "return statement can only occur inside a function at line 2, column 5",
+        ),
+    ]);
+}
+
+#[test]
+fn break_and_skip_outside_a_loop() {
+    assert_errors(&[
+        (
+            "break",
+            "break statement can only occur inside a loop at line 1, column 1",
+        ),
+        (
+            "skip",
+            "skip statement can only occur inside a loop at line 1, column 1",
+        ),
+        // a function does not end the loop it was created in
+        (
+            "loop { f = () => break }",
+            "break statement can only occur inside a loop at line 1, column 18",
+        ),
+    ]);
+}
+
+#[test]
+fn immutable_variables() {
+    assert_errors(&[
+        (
+            "a = 1\na = 2",
+            "cannot mutate Immutable variable 'a' at line 2, column 1",
+        ),
+        (
+            "f = (a) => { a += 1 }",
+            "cannot mutate Immutable variable 'a' at line 1, column 14",
+        ),
+        (
+            "a = 1\nlet a = 2",
+            "Cannot convert from Immutable to Mutable at line 2, column 1",
+        ),
+    ]);
+}
+
+#[test]
+fn nothing_runs_when_checking_fails() {
+    let interpreter = Interpreter::new();
+    assert!(interpreter.evaluate("let ran = true\nprint(b)").is_err());
+    assert!(interpreter.evaluate("let ran = true\nx = (").is_err());
+    assert!(interpreter.evaluate("let ran = true\nx = 1 @ 2").is_err());
+    let err = interpreter.evaluate("ran").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Undefined variable 'ran' at line 1, column 1"
+    );
+}
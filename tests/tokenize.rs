@@ -0,0 +1,116 @@
+use std::io::Cursor;
+
+use prolang::{tokenize, Token, TokenKind};
+
+// `tokenize` promises that the tokens give back their source byte for byte, even when
+// the source has errors. returns the tokens and the messages of the errors
+fn round_trip(source: &str) -> (Vec<Token>, Vec<String>) {
+    let (tokens, errors) = tokenize(Cursor::new(source.to_string().into_bytes()));
+    let rebuilt: String = tokens
+        .iter()
+        .map(|token| {
+            format!(
+                "{}{}{}",
+                token.leading_trivia, token.text, token.trailing_trivia
+            )
+        })
+        .collect();
+    assert_eq!(rebuilt, source);
+    (tokens, errors.iter().map(ToString::to_string).collect())
+}
+
+fn assert_round_trip(source: &str) {
+    let (_, errors) = round_trip(source);
+    assert!(errors.is_empty(), "{source:?} failed: {errors:?}");
+}
+
+#[test]
+fn comments() {
+    assert_round_trip("# a comment\na = 1 # after a statement\n");
+    assert_round_trip("#[ a block\n   comment ]# a = 1\n");
+    assert_round_trip("#[ outer #[ nested ]# still outer ]#\nb = 2");
+}
+
+#[test]
+fn crlf_line_endings() {
+    assert_round_trip("a = 1\r\nb = 2\r\n\r\nprint(a + b)\r\n");
+    assert_round_trip("s = \"\"\"\r\n    text\r\n    \"\"\"\r\n");
+}
+
+#[test]
+fn interpolated_strings() {
+    assert_round_trip("name = \"peter\"\nprint($\"hi {name}, {1 + 2} {{braces}}\")\n");
+    assert_round_trip("print($\"outer {$\"inner {1}\"}\")");
+}
+
+#[test]
+fn raw_and_triple_quoted_strings() {
+    assert_round_trip("path = r\"C:\\new\\table\"\n");
+    assert_round_trip("quote = r#\"she said \"hi\"\"#\n");
+    assert_round_trip("query = \"\"\"\n    SELECT name\n      FROM users\n    \"\"\"\n");
+    assert_round_trip("escaped = \"tab\\t\\u{1F600}\\\"\"\n");
+}
+
+#[test]
+fn non_ascii_identifiers() {
+    assert_round_trip("größe = 1.8\nπ = 3.14\n名前 = \"ピーター\"\nprint(größe * π)\n");
+}
+
+#[test]
+fn whitespace_and_blank_lines() {
+    assert_round_trip("");
+    assert_round_trip("\n\n   \t\n");
+    assert_round_trip("  a   =   1 ;  b = 2   \n\n\tif a { b }   ");
+}
+
+#[test]
+fn sources_with_errors() {
+    let cases = [
+        (
+            "x = 1 @ 2\n",
+            "@",
+            "Invalid character '@' at line 1, column 7",
+        ),
+        (
+            "x = 0x + 1",
+            "0x",
+            "Invalid number '0x' at line 1, column 5",
+        ),
+        (
+            "print(\"\\q\")\n",
+            "\"\\q\"",
+            "Invalid escape sequence '\\q' at line 1, column 8",
+        ),
+        (
+            "s = \"abc\ny = 2",
+            "\"abc\ny = 2",
+            "Unterminated string at line 1, column 5",
+        ),
+    ];
+    for (source, text, expected) in cases {
+        let (tokens, errors) = round_trip(source);
+        assert_eq!(errors, [expected], "{source:?}");
+        let error = tokens
+            .iter()
+            .find(|token| TokenKind::Error == token.kind)
+            .unwrap_or_else(|| panic!("{source:?} has no error token"));
+        assert_eq!(error.text, text, "{source:?}");
+    }
+
+    let (_, errors) = round_trip("a = 1\r b = 2 #[ never closed\n");
+    assert_eq!(
+        errors,
+        [
+            "Invalid character '\\r' at line 1, column 6",
+            "Unterminated block comment at line 1, column 14",
+        ]
+    );
+    let (_, errors) = round_trip("x = $\"a {1 ~ 2}");
+    assert_eq!(
+        errors,
+        [
+            "Unterminated string at line 1, column 5",
+            "Invalid character '~' at line 1, column 12",
+        ]
+    );
+}